@@ -0,0 +1,123 @@
+//! Compares the calibration values extraction of Part 2 with the [`DigitMatcher`]
+//! automaton, the naive scan of every word at every position, and the original
+//! implementation that grows a `temporary` string one character at a time.
+//!
+//! Run with `cargo bench --package puzzle_2023_day_1`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use puzzle_2023_day_1::{DigitMatcher, Language, Vocabulary};
+
+const REPETITIONS: usize = 200;
+
+fn part_2_original(input: &str) -> usize {
+    let numbers_spelled_out_with_letters = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+
+    input
+        .lines()
+        .map(|line| {
+            let mut characters_digits: Vec<char> = vec![];
+            let mut temporary = String::from("");
+            for character in line.chars() {
+                temporary += &character.to_string();
+
+                let mut temporary_spelled_number_index = None;
+                for (index, spelled_number) in numbers_spelled_out_with_letters.iter().enumerate() {
+                    if temporary.contains(spelled_number) {
+                        temporary_spelled_number_index = Some(index);
+                        break;
+                    }
+                }
+                if let Some(temporary_spelled_number_index) = temporary_spelled_number_index {
+                    let number = temporary_spelled_number_index + 1;
+                    characters_digits.push(
+                        number
+                            .to_string()
+                            .chars()
+                            .next()
+                            .expect("Number should be single-character digit."),
+                    );
+                    temporary = character.to_string();
+                }
+
+                if character.is_ascii_digit() {
+                    characters_digits.push(character);
+                    temporary = String::from("");
+                }
+            }
+
+            let first_digit = characters_digits.first().unwrap_or(&'0').to_owned();
+            let last_digit = characters_digits.last().unwrap_or(&'0').to_owned();
+            let number = format!("{}{}", first_digit, last_digit);
+            let number: usize = number.parse().expect("Should parse as a `usize`.");
+            number
+        })
+        .sum()
+}
+
+fn part_2_naive(input: &str, vocabulary: &Vocabulary) -> usize {
+    input
+        .lines()
+        .map(|line| {
+            let digits = line
+                .char_indices()
+                .filter_map(|(index, character)| {
+                    character
+                        .to_digit(10)
+                        .or_else(|| vocabulary.spelled_digit_at_start(&line[index..]))
+                })
+                .collect::<Vec<u32>>();
+            let first_digit = digits.first().copied().unwrap_or_default();
+            let last_digit = digits.last().copied().unwrap_or_default();
+            (first_digit * 10 + last_digit) as usize
+        })
+        .sum()
+}
+
+fn part_2_automaton(input: &str, matcher: &DigitMatcher) -> usize {
+    input
+        .lines()
+        .map(|line| matcher.calibration_value(line))
+        .sum()
+}
+
+fn measure(name: &str, lines_count: usize, function: impl Fn() -> usize) -> usize {
+    let mut best = Duration::MAX;
+    let mut result = 0;
+    for _ in 0..5 {
+        let start = Instant::now();
+        result = black_box(function());
+        best = best.min(start.elapsed());
+    }
+    println!(
+        "{:<10} {:>10.2?} ({:>6.1} ns/line)",
+        name,
+        best,
+        best.as_nanos() as f64 / lines_count as f64
+    );
+    result
+}
+
+fn main() {
+    let input = include_str!("../input.txt").repeat(REPETITIONS);
+    let lines_count = input.lines().count();
+    let vocabulary = Vocabulary::from(Language::English);
+    let matcher = DigitMatcher::new(&vocabulary);
+    println!("Part 2 on {} lines (best of 5 runs):", lines_count);
+
+    let original = measure("original", lines_count, || {
+        part_2_original(black_box(&input))
+    });
+    let naive = measure("naive", lines_count, || {
+        part_2_naive(black_box(&input), &vocabulary)
+    });
+    let automaton = measure("automaton", lines_count, || {
+        part_2_automaton(black_box(&input), &matcher)
+    });
+
+    assert_eq!(original, naive);
+    assert_eq!(original, automaton);
+}
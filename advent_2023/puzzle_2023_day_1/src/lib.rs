@@ -1,66 +1,520 @@
-pub fn part_1(input: &str) -> usize {
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::fmt;
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum Language {
+    #[default]
+    English,
+    French,
+    German,
+    Spanish,
+}
+
+/// Words spelling out digits, that also count as valid digits in a calibration line.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Vocabulary {
+    pub spelled_digits: Vec<(String, u32)>,
+}
+
+impl Vocabulary {
+    /// Returns a vocabulary where only numerals (`1`, `2`, ...) are digits.
+    pub fn numerals_only() -> Self {
+        Vocabulary::default()
+    }
+
+    /// Returns a vocabulary from custom words, e.g. `[("zwo", 2)]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_1::{calibration_value, Language, Vocabulary};
+    ///
+    /// let mut vocabulary = Vocabulary::from(Language::German);
+    /// vocabulary.spelled_digits.extend(Vocabulary::new([("zwo", 2)]).spelled_digits);
+    ///
+    /// assert_eq!(calibration_value("dreizwo", &vocabulary), 32);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a word spells out a number that is not a digit, i.e. greater than 9.
+    pub fn new<'a>(spelled_digits: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        Vocabulary {
+            spelled_digits: spelled_digits
+                .into_iter()
+                .map(|(word, digit)| {
+                    assert!(
+                        digit <= 9,
+                        "Word `{word}` should spell out a digit, not {digit}."
+                    );
+                    (String::from(word), digit)
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the digit spelled out by the longest word starting at the beginning of `string`.
+    ///
+    /// Naive counterpart of [`DigitMatcher`], comparing every word at every position.
+    pub fn spelled_digit_at_start(&self, string: &str) -> Option<u32> {
+        self.spelled_digits
+            .iter()
+            .filter(|(word, _)| string.starts_with(word.as_str()))
+            .max_by_key(|(word, _)| word.len())
+            .map(|&(_, digit)| digit)
+    }
+}
+
+impl From<Language> for Vocabulary {
+    fn from(language: Language) -> Self {
+        let words = match language {
+            Language::English => [
+                "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+            ],
+            Language::French => [
+                "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+            ],
+            Language::German => [
+                "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+            ],
+            Language::Spanish => [
+                "uno", "dos", "tres", "cuatro", "cinco", "seis", "siete", "ocho", "nueve",
+            ],
+        };
+        Vocabulary::new(words.into_iter().zip(1..))
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum DigitSource {
+    #[default]
+    Numeral,
+    Word,
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub struct DigitOccurrence {
+    /// Byte offset in the line where the numeral or the spelled digit starts.
+    pub byte_offset: usize,
+    pub digit: u32,
+    pub source: DigitSource,
+}
+
+/// How the digits of a line are combined into its calibration value.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum ValueRule {
+    /// The first digit and the last digit, in that order, e.g. `"a1b2c3"` is `13`.
+    #[default]
+    FirstAndLast,
+    /// Every digit, in order, e.g. `"a1b2c3"` is `123`.
+    AllDigits,
+    /// The first `N` digits (or fewer if the line has fewer), e.g. `"a1b2c3"` is `12` for `N = 2`.
+    FirstN(usize),
+}
+
+impl ValueRule {
+    /// Returns the number formed by the `digits` selected by the rule, saturating
+    /// at [`usize::MAX`] when it does not fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_1::ValueRule;
+    ///
+    /// assert_eq!(ValueRule::FirstAndLast.value(&[7]), 77);
+    /// assert_eq!(ValueRule::AllDigits.value(&[1, 0, 3]), 103);
+    /// assert_eq!(ValueRule::FirstN(2).value(&[4, 9, 8]), 49);
+    /// assert_eq!(ValueRule::FirstN(2).value(&[]), 0);
+    /// ```
+    pub fn value(&self, digits: &[u32]) -> usize {
+        let selected_digits = match *self {
+            ValueRule::FirstAndLast => match (digits.first(), digits.last()) {
+                (Some(&first), Some(&last)) => vec![first, last],
+                _ => vec![],
+            },
+            ValueRule::AllDigits => digits.to_vec(),
+            ValueRule::FirstN(count) => digits.iter().take(count).copied().collect(),
+        };
+        selected_digits
+            .iter()
+            .fold(0, |accumulator: usize, &digit| {
+                accumulator
+                    .saturating_mul(10)
+                    .saturating_add(digit as usize)
+            })
+    }
+}
+
+const ROOT_STATE: usize = 0;
+
+const NO_STATE: usize = usize::MAX;
+
+/// Aho-Corasick automaton finding numerals and the spelled digits of a [`Vocabulary`]
+/// in a single pass over the bytes of a line.
+///
+/// When several words start at the same byte offset, the longest one wins.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct DigitMatcher {
+    /// Next state for each state and each byte.
+    transitions: Vec<[usize; 256]>,
+    /// Length in bytes, digit and source of each pattern ending in each state.
+    outputs: Vec<Vec<(usize, u32, DigitSource)>>,
+}
+
+impl DigitMatcher {
+    /// # Panics
+    ///
+    /// Panics if a word of the `vocabulary` spells out a number greater than 9.
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let mut matcher = DigitMatcher {
+            transitions: vec![[NO_STATE; 256]],
+            outputs: vec![vec![]],
+        };
+        for digit in 0..10 {
+            matcher.insert(digit.to_string().as_bytes(), digit, DigitSource::Numeral);
+        }
+        for (word, digit) in &vocabulary.spelled_digits {
+            assert!(
+                *digit <= 9,
+                "Word `{word}` should spell out a digit, not {digit}."
+            );
+            if !word.is_empty() {
+                matcher.insert(word.as_bytes(), *digit, DigitSource::Word);
+            }
+        }
+        matcher.build_failure_transitions();
+        matcher
+    }
+
+    fn insert(&mut self, pattern: &[u8], digit: u32, source: DigitSource) {
+        let mut state = ROOT_STATE;
+        for &byte in pattern {
+            if self.transitions[state][byte as usize] == NO_STATE {
+                self.transitions.push([NO_STATE; 256]);
+                self.outputs.push(vec![]);
+                self.transitions[state][byte as usize] = self.transitions.len() - 1;
+            }
+            state = self.transitions[state][byte as usize];
+        }
+        self.outputs[state].push((pattern.len(), digit, source));
+    }
+
+    /// Turns the trie into a complete automaton: missing transitions follow the
+    /// failure link, i.e. the longest proper suffix that is also a prefix of a pattern.
+    fn build_failure_transitions(&mut self) {
+        let mut failures = vec![ROOT_STATE; self.transitions.len()];
+        let mut queue = VecDeque::new();
+        for byte in 0..256 {
+            let next_state = self.transitions[ROOT_STATE][byte];
+            if next_state == NO_STATE {
+                self.transitions[ROOT_STATE][byte] = ROOT_STATE;
+            } else {
+                queue.push_back(next_state);
+            }
+        }
+        while let Some(state) = queue.pop_front() {
+            let failure = failures[state];
+            let failure_outputs = self.outputs[failure].clone();
+            self.outputs[state].extend(failure_outputs);
+            for byte in 0..256 {
+                let next_state = self.transitions[state][byte];
+                if next_state == NO_STATE {
+                    self.transitions[state][byte] = self.transitions[failure][byte];
+                } else {
+                    failures[next_state] = self.transitions[failure][byte];
+                    queue.push_back(next_state);
+                }
+            }
+        }
+    }
+
+    /// Returns every match in the `line` with its length in bytes, including
+    /// overlapping ones, ordered by the byte offset where they end.
+    fn matches<'a>(&'a self, line: &'a str) -> impl Iterator<Item = (usize, DigitOccurrence)> + 'a {
+        line.bytes()
+            .enumerate()
+            .scan(ROOT_STATE, move |state, (index, byte)| {
+                *state = self.transitions[*state][byte as usize];
+                Some((index, *state))
+            })
+            .flat_map(move |(index, state)| {
+                self.outputs[state]
+                    .iter()
+                    .map(move |&(length, digit, source)| {
+                        let occurrence = DigitOccurrence {
+                            byte_offset: index + 1 - length,
+                            digit,
+                            source,
+                        };
+                        (length, occurrence)
+                    })
+            })
+    }
+
+    /// Returns every match in the `line`, including overlapping ones, ordered by
+    /// the byte offset where they end.
+    pub fn find_iter<'a>(&'a self, line: &'a str) -> impl Iterator<Item = DigitOccurrence> + 'a {
+        self.matches(line).map(|(_, occurrence)| occurrence)
+    }
+
+    /// Returns the digits of the `line` ordered by byte offset, one per byte offset.
+    ///
+    /// Spelled digits may overlap, so `"eightwo"` contains both `8` and `2`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_1::{DigitMatcher, Language, Vocabulary};
+    ///
+    /// let matcher = DigitMatcher::new(&Vocabulary::from(Language::English));
+    /// let digits = matcher
+    ///     .occurrences("xtwone3four")
+    ///     .iter()
+    ///     .map(|occurrence| (occurrence.byte_offset, occurrence.digit))
+    ///     .collect::<Vec<(usize, u32)>>();
+    ///
+    /// assert_eq!(digits, vec![(1, 2), (3, 1), (6, 3), (7, 4)]);
+    /// ```
+    pub fn occurrences(&self, line: &str) -> Vec<DigitOccurrence> {
+        let mut matches = self
+            .matches(line)
+            .collect::<Vec<(usize, DigitOccurrence)>>();
+        matches.sort_by_key(|&(length, occurrence)| (occurrence.byte_offset, Reverse(length)));
+        matches.dedup_by_key(|(_, occurrence)| occurrence.byte_offset);
+        matches
+            .into_iter()
+            .map(|(_, occurrence)| occurrence)
+            .collect()
+    }
+
+    /// Returns the first and the last digits of the `line`, in a single pass.
+    pub fn first_and_last(&self, line: &str) -> Option<(DigitOccurrence, DigitOccurrence)> {
+        let mut first: Option<(usize, DigitOccurrence)> = None;
+        let mut last: Option<(usize, DigitOccurrence)> = None;
+        for (length, occurrence) in self.matches(line) {
+            let is_before_first = first.is_none_or(|(first_length, first)| {
+                (occurrence.byte_offset, Reverse(length))
+                    < (first.byte_offset, Reverse(first_length))
+            });
+            if is_before_first {
+                first = Some((length, occurrence));
+            }
+            let is_after_last = last.is_none_or(|(last_length, last)| {
+                (occurrence.byte_offset, length) > (last.byte_offset, last_length)
+            });
+            if is_after_last {
+                last = Some((length, occurrence));
+            }
+        }
+        Some((first?.1, last?.1))
+    }
+
+    /// Combines the first digit and the last digit of the `line` to form a two-digit number.
+    pub fn calibration_value(&self, line: &str) -> usize {
+        self.calibration_value_with_rule(line, ValueRule::FirstAndLast)
+    }
+
+    pub fn calibration_value_with_rule(&self, line: &str, value_rule: ValueRule) -> usize {
+        let digits = match value_rule {
+            ValueRule::FirstAndLast => self
+                .first_and_last(line)
+                .map(|(first, last)| vec![first.digit, last.digit])
+                .unwrap_or_default(),
+            ValueRule::AllDigits | ValueRule::FirstN(_) => self
+                .occurrences(line)
+                .iter()
+                .map(|occurrence| occurrence.digit)
+                .collect(),
+        };
+        value_rule.value(&digits)
+    }
+}
+
+/// Every digit found in a line of the calibration document, and the value it contributes.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct LineCalibration {
+    /// Line number (starting at 1) in the calibration document.
+    pub line_number: usize,
+    pub occurrences: Vec<DigitOccurrence>,
+    pub value: usize,
+}
+
+/// Returns, for every line of the `input`, its digits and its value according to `value_rule`.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_1::{audit_calibration, DigitSource, Language, ValueRule, Vocabulary};
+///
+/// let lines_calibrations = audit_calibration(
+///     "two1nine\n7pqrstsixteen",
+///     &Vocabulary::from(Language::English),
+///     ValueRule::AllDigits,
+/// );
+///
+/// assert_eq!(lines_calibrations[0].value, 219);
+/// assert_eq!(lines_calibrations[1].line_number, 2);
+/// assert_eq!(lines_calibrations[1].occurrences[1].byte_offset, 6);
+/// assert_eq!(lines_calibrations[1].occurrences[1].source, DigitSource::Word);
+/// ```
+pub fn audit_calibration(
+    input: &str,
+    vocabulary: &Vocabulary,
+    value_rule: ValueRule,
+) -> Vec<LineCalibration> {
+    let matcher = DigitMatcher::new(vocabulary);
     input
         .lines()
-        .map(|line| {
-            let characters_digits = line
-                .chars()
-                .filter(|&character| character.is_ascii_digit())
-                .collect::<Vec<char>>();
-
-            let first_digit = characters_digits.first().unwrap_or(&'0').to_owned();
-            let last_digit = characters_digits.last().unwrap_or(&'0').to_owned();
-            let number = format!("{}{}", first_digit, last_digit);
-            let number: usize = number.parse().expect("Should parse as a `usize`.");
-            number
+        .enumerate()
+        .map(|(index_line, line)| {
+            let occurrences = matcher.occurrences(line);
+            let digits = occurrences
+                .iter()
+                .map(|occurrence| occurrence.digit)
+                .collect::<Vec<u32>>();
+            LineCalibration {
+                line_number: index_line + 1,
+                occurrences,
+                value: value_rule.value(&digits),
+            }
         })
-        .sum()
+        .collect()
 }
 
-pub fn part_2(input: &str) -> usize {
-    let numbers_spelled_out_with_letters = [
-        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-    ];
+/// Returns every digit of the `line`, numerals or words of the `vocabulary`, in order.
+///
+/// Spelled digits may overlap, so `"eightwo"` contains both `8` and `2`.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_1::{extract_digits, Language, Vocabulary};
+///
+/// assert_eq!(extract_digits("xtwone3four", &Vocabulary::from(Language::English)), vec![2, 1, 3, 4]);
+/// assert_eq!(extract_digits("septrois", &Vocabulary::from(Language::French)), vec![7, 3]);
+/// assert_eq!(extract_digits("xtwone3four", &Vocabulary::numerals_only()), vec![3]);
+/// ```
+pub fn extract_digits(line: &str, vocabulary: &Vocabulary) -> Vec<u32> {
+    DigitMatcher::new(vocabulary)
+        .occurrences(line)
+        .iter()
+        .map(|occurrence| occurrence.digit)
+        .collect()
+}
+
+/// Combines the first digit and the last digit of the `line` to form a two-digit number.
+pub fn calibration_value(line: &str, vocabulary: &Vocabulary) -> usize {
+    DigitMatcher::new(vocabulary).calibration_value(line)
+}
 
+pub fn sum_calibration_values(input: &str, vocabulary: &Vocabulary) -> usize {
+    let matcher = DigitMatcher::new(vocabulary);
     input
         .lines()
-        .map(|line| {
-            let mut characters_digits: Vec<char> = vec![];
-            let mut temporary = String::from("");
-            for character in line.chars() {
-                temporary += &character.to_string();
-
-                let mut temporary_spelled_number_index = None;
-                for (index, spelled_number) in numbers_spelled_out_with_letters.iter().enumerate() {
-                    if temporary.contains(spelled_number) {
-                        temporary_spelled_number_index = Some(index);
-                        break;
-                    }
-                }
-                if let Some(temporary_spelled_number_index) = temporary_spelled_number_index {
-                    let number = temporary_spelled_number_index + 1;
-                    characters_digits.push(
-                        number
-                            .to_string()
-                            .chars()
-                            .next()
-                            .expect("Number should be single-character digit."),
-                    );
-                    temporary = character.to_string();
-                }
+        .map(|line| matcher.calibration_value(line))
+        .sum()
+}
 
-                if character.is_ascii_digit() {
-                    characters_digits.push(character);
-                    temporary = String::from("");
-                }
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum LineWithoutDigitsKind {
+    Empty,
+    NoDigit,
+}
+
+/// Line of the calibration document without any digit, that contributes `0` to the sum.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LineWithoutDigits {
+    /// Line number (starting at 1) in the calibration document.
+    pub line_number: usize,
+    pub line: String,
+    pub kind: LineWithoutDigitsKind,
+}
+
+impl fmt::Display for LineWithoutDigits {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            LineWithoutDigitsKind::Empty => {
+                write!(formatter, "Line {} is empty.", self.line_number)
             }
+            LineWithoutDigitsKind::NoDigit => write!(
+                formatter,
+                "Line {} has no digit (`{}`).",
+                self.line_number, self.line
+            ),
+        }
+    }
+}
 
-            let first_digit = characters_digits.first().unwrap_or(&'0').to_owned();
-            let last_digit = characters_digits.last().unwrap_or(&'0').to_owned();
-            let number = format!("{}{}", first_digit, last_digit);
-            let number: usize = number.parse().expect("Should parse as a `usize`.");
-            number
-        })
-        .sum()
+impl std::error::Error for LineWithoutDigits {}
+
+/// Sum of the calibration values, with the lines without digits to warn about.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Calibration {
+    pub sum: usize,
+    pub lines_without_digits: Vec<LineWithoutDigits>,
+}
+
+/// Sums the calibration values of the `input`, reporting every line without digits
+/// instead of silently counting it as `0`.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_1::{calibrate, LineWithoutDigits, LineWithoutDigitsKind, ValueRule, Vocabulary};
+///
+/// let calibration = calibrate("1abc2\nabc\ntreb7uchet", &Vocabulary::numerals_only(), ValueRule::FirstAndLast);
+///
+/// assert_eq!(calibration.sum, 12 + 77);
+/// assert_eq!(
+///     calibration.lines_without_digits,
+///     vec![LineWithoutDigits {
+///         line_number: 2,
+///         line: String::from("abc"),
+///         kind: LineWithoutDigitsKind::NoDigit,
+///     }],
+/// );
+/// ```
+pub fn calibrate(input: &str, vocabulary: &Vocabulary, value_rule: ValueRule) -> Calibration {
+    let mut result = Calibration::default();
+    let lines_calibrations = audit_calibration(input, vocabulary, value_rule);
+    for (line, line_calibration) in input.lines().zip(lines_calibrations) {
+        result.sum += line_calibration.value;
+        if line_calibration.occurrences.is_empty() {
+            result.lines_without_digits.push(LineWithoutDigits {
+                line_number: line_calibration.line_number,
+                line: String::from(line),
+                kind: if line.trim().is_empty() {
+                    LineWithoutDigitsKind::Empty
+                } else {
+                    LineWithoutDigitsKind::NoDigit
+                },
+            });
+        }
+    }
+    result
+}
+
+/// Sums the calibration values of the `input` like [`calibrate`], but fails on the
+/// first line without digits.
+pub fn calibrate_strict(
+    input: &str,
+    vocabulary: &Vocabulary,
+    value_rule: ValueRule,
+) -> Result<usize, LineWithoutDigits> {
+    let calibration = calibrate(input, vocabulary, value_rule);
+    match calibration.lines_without_digits.into_iter().next() {
+        Some(line_without_digits) => Err(line_without_digits),
+        None => Ok(calibration.sum),
+    }
+}
+
+pub fn part_1(input: &str) -> usize {
+    sum_calibration_values(input, &Vocabulary::numerals_only())
+}
+
+pub fn part_2(input: &str) -> usize {
+    sum_calibration_values(input, &Vocabulary::from(Language::English))
 }
 
 #[cfg(test)]
@@ -86,4 +540,338 @@ mod puzzle_2023_day_1_tests {
     fn test_part_2() {
         assert_eq!(part_2(include_str!("../input.txt")), 54985);
     }
+
+    mod vocabularies {
+        use crate::{calibration_value, extract_digits, Language, Vocabulary};
+
+        #[test]
+        fn test_overlapping_words_in_every_language() {
+            let overlapping_lines = [
+                (Language::English, "eightwo", vec![8, 2]),
+                (Language::English, "oneight", vec![1, 8]),
+                (Language::French, "septrois", vec![7, 3]),
+                (Language::French, "cinquatre", vec![5, 4]),
+                (Language::German, "dreins", vec![3, 1]),
+                (Language::German, "siebeneun", vec![7, 9]),
+                (Language::German, "sechsieben", vec![6, 7]),
+                (Language::Spanish, "dosiete", vec![2, 7]),
+                (Language::Spanish, "unocho", vec![1, 8]),
+            ];
+            for (language, line, expected) in overlapping_lines {
+                assert_eq!(
+                    extract_digits(line, &Vocabulary::from(language)),
+                    expected,
+                    "{}",
+                    line
+                );
+            }
+        }
+
+        #[test]
+        fn test_example_translated() {
+            let lines = [
+                (Language::French, "deux1neuf", 29),
+                (Language::French, "huitdeuxtrois", 83),
+                (Language::German, "abceins2dreixyz", 13),
+                (Language::German, "xfünf3vier", 54),
+                (Language::Spanish, "4nueveochosiete2", 42),
+                (Language::Spanish, "zunocho234", 14),
+            ];
+            for (language, line, expected) in lines {
+                assert_eq!(
+                    calibration_value(line, &Vocabulary::from(language)),
+                    expected,
+                    "{}",
+                    line
+                );
+            }
+        }
+
+        #[test]
+        fn test_custom_vocabulary_with_overlapping_words() {
+            let vocabulary = Vocabulary::new([("abab", 1), ("baba", 2)]);
+            assert_eq!(extract_digits("ababab", &vocabulary), vec![1, 2, 1]);
+        }
+    }
+
+    mod audit {
+        use crate::{
+            audit_calibration, sum_calibration_values, DigitOccurrence, DigitSource, Language,
+            ValueRule, Vocabulary,
+        };
+
+        #[test]
+        fn test_occurrences_with_offsets_and_sources() {
+            let lines_calibrations = audit_calibration(
+                "xtwone3four",
+                &Vocabulary::from(Language::English),
+                ValueRule::FirstAndLast,
+            );
+            assert_eq!(
+                lines_calibrations[0].occurrences,
+                vec![
+                    DigitOccurrence {
+                        byte_offset: 1,
+                        digit: 2,
+                        source: DigitSource::Word,
+                    },
+                    DigitOccurrence {
+                        byte_offset: 3,
+                        digit: 1,
+                        source: DigitSource::Word,
+                    },
+                    DigitOccurrence {
+                        byte_offset: 6,
+                        digit: 3,
+                        source: DigitSource::Numeral,
+                    },
+                    DigitOccurrence {
+                        byte_offset: 7,
+                        digit: 4,
+                        source: DigitSource::Word,
+                    },
+                ]
+            );
+            assert_eq!(lines_calibrations[0].value, 24);
+        }
+
+        #[test]
+        fn test_audit_sums_like_part_2() {
+            let vocabulary = Vocabulary::from(Language::English);
+            let input = include_str!("../input.txt");
+            let total = audit_calibration(input, &vocabulary, ValueRule::FirstAndLast)
+                .iter()
+                .map(|line_calibration| line_calibration.value)
+                .sum::<usize>();
+            assert_eq!(total, sum_calibration_values(input, &vocabulary));
+            assert_eq!(total, 54985);
+        }
+
+        #[test]
+        fn test_value_rules_example() {
+            let vocabulary = Vocabulary::from(Language::English);
+            let values = |value_rule| {
+                audit_calibration(
+                    include_str!("../input_example_2.txt"),
+                    &vocabulary,
+                    value_rule,
+                )
+                .iter()
+                .map(|line_calibration| line_calibration.value)
+                .collect::<Vec<usize>>()
+            };
+            assert_eq!(
+                values(ValueRule::FirstAndLast),
+                vec![29, 83, 13, 24, 42, 14, 76]
+            );
+            assert_eq!(
+                values(ValueRule::AllDigits),
+                vec![219, 823, 123, 2134, 49872, 18234, 76]
+            );
+            assert_eq!(values(ValueRule::FirstN(1)), vec![2, 8, 1, 2, 4, 1, 7]);
+            assert_eq!(
+                values(ValueRule::FirstN(3)),
+                vec![219, 823, 123, 213, 498, 182, 76]
+            );
+        }
+    }
+
+    mod lines_without_digits {
+        use crate::{
+            calibrate, calibrate_strict, Language, LineWithoutDigits, LineWithoutDigitsKind,
+            ValueRule, Vocabulary,
+        };
+
+        #[test]
+        fn test_warnings_example() {
+            let input = include_str!("../input_example_2.txt");
+            let calibration =
+                calibrate(input, &Vocabulary::numerals_only(), ValueRule::FirstAndLast);
+            assert_eq!(
+                calibration.lines_without_digits,
+                vec![LineWithoutDigits {
+                    line_number: 2,
+                    line: String::from("eightwothree"),
+                    kind: LineWithoutDigitsKind::NoDigit,
+                }]
+            );
+            let calibration = calibrate(
+                input,
+                &Vocabulary::from(Language::English),
+                ValueRule::FirstAndLast,
+            );
+            assert_eq!(calibration.sum, 281);
+            assert!(calibration.lines_without_digits.is_empty());
+        }
+
+        #[test]
+        fn test_empty_lines() {
+            let calibration = calibrate(
+                "1abc2\n\n  \nxyz",
+                &Vocabulary::numerals_only(),
+                ValueRule::FirstAndLast,
+            );
+            let kinds = calibration
+                .lines_without_digits
+                .iter()
+                .map(|line_without_digits| {
+                    (line_without_digits.line_number, line_without_digits.kind)
+                })
+                .collect::<Vec<(usize, LineWithoutDigitsKind)>>();
+            assert_eq!(
+                kinds,
+                vec![
+                    (2, LineWithoutDigitsKind::Empty),
+                    (3, LineWithoutDigitsKind::Empty),
+                    (4, LineWithoutDigitsKind::NoDigit),
+                ]
+            );
+            assert_eq!(calibration.sum, 12);
+        }
+
+        #[test]
+        fn test_strict_mode() {
+            let vocabulary = Vocabulary::numerals_only();
+            assert_eq!(
+                calibrate_strict(
+                    include_str!("../input.txt"),
+                    &vocabulary,
+                    ValueRule::FirstAndLast
+                ),
+                Ok(55130)
+            );
+            let error = calibrate_strict(
+                include_str!("../input_example_2.txt"),
+                &vocabulary,
+                ValueRule::FirstAndLast,
+            )
+            .unwrap_err();
+            assert_eq!(error.line_number, 2);
+            assert_eq!(error.to_string(), "Line 2 has no digit (`eightwothree`).");
+        }
+    }
+
+    mod digit_matcher {
+        use crate::{
+            audit_calibration, sum_calibration_values, DigitMatcher, DigitOccurrence, DigitSource,
+            Language, ValueRule, Vocabulary,
+        };
+
+        #[test]
+        fn test_matches_naive_extraction() {
+            for language in [
+                Language::English,
+                Language::French,
+                Language::German,
+                Language::Spanish,
+            ] {
+                let vocabulary = Vocabulary::from(language);
+                let matcher = DigitMatcher::new(&vocabulary);
+                for line in include_str!("../input.txt").lines() {
+                    let expected = line
+                        .char_indices()
+                        .filter_map(|(index, character)| {
+                            character
+                                .to_digit(10)
+                                .or_else(|| vocabulary.spelled_digit_at_start(&line[index..]))
+                        })
+                        .collect::<Vec<u32>>();
+                    let actual = matcher
+                        .occurrences(line)
+                        .iter()
+                        .map(|occurrence| occurrence.digit)
+                        .collect::<Vec<u32>>();
+                    assert_eq!(actual, expected, "{}", line);
+                }
+            }
+        }
+
+        #[test]
+        #[should_panic(expected = "should spell out a digit")]
+        fn test_vocabulary_rejects_numbers() {
+            Vocabulary::new([("dix", 10)]);
+        }
+
+        #[test]
+        #[should_panic(expected = "should spell out a digit")]
+        fn test_matcher_rejects_numbers() {
+            let vocabulary = Vocabulary {
+                spelled_digits: vec![(String::from("dix"), 10)],
+            };
+            DigitMatcher::new(&vocabulary);
+        }
+
+        #[test]
+        fn test_longest_word_wins_at_the_same_offset() {
+            let matcher = DigitMatcher::new(&Vocabulary::new([("ab", 1), ("abcd", 2), ("bc", 3)]));
+            assert_eq!(
+                matcher.occurrences("xabcd"),
+                vec![
+                    DigitOccurrence {
+                        byte_offset: 1,
+                        digit: 2,
+                        source: DigitSource::Word,
+                    },
+                    DigitOccurrence {
+                        byte_offset: 2,
+                        digit: 3,
+                        source: DigitSource::Word,
+                    },
+                ]
+            );
+            assert_eq!(
+                matcher.first_and_last("xabcd"),
+                Some((
+                    DigitOccurrence {
+                        byte_offset: 1,
+                        digit: 2,
+                        source: DigitSource::Word,
+                    },
+                    DigitOccurrence {
+                        byte_offset: 2,
+                        digit: 3,
+                        source: DigitSource::Word,
+                    },
+                ))
+            );
+
+            let vocabulary = Vocabulary::new([("ab", 1), ("abcd", 2)]);
+            let matcher = DigitMatcher::new(&vocabulary);
+            let longest_word = DigitOccurrence {
+                byte_offset: 1,
+                digit: 2,
+                source: DigitSource::Word,
+            };
+            assert_eq!(
+                matcher.first_and_last("xabcd"),
+                Some((longest_word, longest_word))
+            );
+            assert_eq!(sum_calibration_values("xabcd", &vocabulary), 22);
+            assert_eq!(
+                audit_calibration("xabcd", &vocabulary, ValueRule::FirstAndLast)[0].value,
+                22
+            );
+        }
+
+        #[test]
+        fn test_multi_byte_words() {
+            let matcher = DigitMatcher::new(&Vocabulary::from(Language::German));
+            assert_eq!(
+                matcher.first_and_last("éfünfzwölf"),
+                Some((
+                    DigitOccurrence {
+                        byte_offset: 2,
+                        digit: 5,
+                        source: DigitSource::Word,
+                    },
+                    DigitOccurrence {
+                        byte_offset: 2,
+                        digit: 5,
+                        source: DigitSource::Word,
+                    },
+                ))
+            );
+            assert_eq!(matcher.first_and_last("nothing"), None);
+        }
+    }
 }
@@ -1,14 +1,60 @@
+use std::collections::BTreeMap;
+use std::fmt;
 use std::str::FromStr;
 
-const NUMBER_OF_RED_CUBES_IN_THE_BAG: usize = 12;
-const NUMBER_OF_GREEN_CUBES_IN_THE_BAG: usize = 13;
-const NUMBER_OF_BLUE_CUBES_IN_THE_BAG: usize = 14;
-
-#[derive(Debug, Default, PartialEq)]
+/// Multiset of cubes, keyed by their color.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
 pub struct NumberOfCubesOfEachColor {
-    pub red: usize,
-    pub green: usize,
-    pub blue: usize,
+    pub counts: BTreeMap<String, usize>,
+}
+
+impl<const N: usize> From<[(&str, usize); N]> for NumberOfCubesOfEachColor {
+    fn from(counts: [(&str, usize); N]) -> Self {
+        NumberOfCubesOfEachColor {
+            counts: counts
+                .into_iter()
+                .map(|(color, count)| (String::from(color), count))
+                .collect(),
+        }
+    }
+}
+
+impl NumberOfCubesOfEachColor {
+    pub fn get(&self, color: &str) -> usize {
+        self.counts.get(color).copied().unwrap_or_default()
+    }
+
+    /// Returns `true` when there are at most as many cubes of each color as in `other`.
+    pub fn is_subset_of(&self, other: &NumberOfCubesOfEachColor) -> bool {
+        self.counts
+            .iter()
+            .all(|(color, &count)| count <= other.get(color))
+    }
+
+    /// Returns, for each color, the maximum number of cubes between `self` and `other`.
+    pub fn union(&self, other: &NumberOfCubesOfEachColor) -> NumberOfCubesOfEachColor {
+        let mut result = self.clone();
+        for (color, &count) in &other.counts {
+            let result_count = result.counts.entry(color.to_owned()).or_default();
+            *result_count = (*result_count).max(count);
+        }
+        result
+    }
+
+    /// Returns the numbers of cubes of each color multiplied together, a color without
+    /// cubes counting as 1 whether it is absent or revealed as 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_2::NumberOfCubesOfEachColor;
+    ///
+    /// assert_eq!(NumberOfCubesOfEachColor::from([("red", 4), ("blue", 6)]).power(), 24);
+    /// assert_eq!(NumberOfCubesOfEachColor::from([("red", 0), ("blue", 6)]).power(), 6);
+    /// ```
+    pub fn power(&self) -> usize {
+        self.counts.values().map(|&count| count.max(1)).product()
+    }
 }
 
 impl FromStr for NumberOfCubesOfEachColor {
@@ -26,29 +72,101 @@ impl FromStr for NumberOfCubesOfEachColor {
     /// use std::str::FromStr;
     /// use puzzle_2023_day_2::NumberOfCubesOfEachColor;
     ///
-    /// let string = "3 blue, 4 red";
-    /// let expected_result = NumberOfCubesOfEachColor {
-    ///     red: 4,
-    ///     green: 0,
-    ///     blue: 3,
-    /// };
+    /// let string = "3 blue, 4 red, 1 yellow, 2 red";
+    /// let expected_result = NumberOfCubesOfEachColor::from([("red", 6), ("blue", 3), ("yellow", 1)]);
     /// let actual_result = NumberOfCubesOfEachColor::from_str(string).unwrap();
     ///
     /// assert_eq!(actual_result, expected_result);
     /// ```
     fn from_str(string: &str) -> Result<Self, Self::Err> {
+        NumberOfCubesOfEachColor::parse(string, DuplicateColors::default())
+            .map_err(|error| error.kind.message())
+    }
+}
+
+impl NumberOfCubesOfEachColor {
+    /// Parses a revealed subset of cubes such as `"3 blue, 4 red"`, handling a color
+    /// appearing more than once according to `duplicate_colors`.
+    ///
+    /// The error is located on line 1, at the column of the offending text in `string`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_2::{DuplicateColors, GameParseError, GameParseErrorKind, NumberOfCubesOfEachColor};
+    ///
+    /// assert_eq!(
+    ///     NumberOfCubesOfEachColor::parse("3 red, 2 red", DuplicateColors::Sum),
+    ///     Ok(NumberOfCubesOfEachColor::from([("red", 5)])),
+    /// );
+    /// assert_eq!(
+    ///     NumberOfCubesOfEachColor::parse("3 red, 2 red", DuplicateColors::Reject),
+    ///     Err(GameParseError {
+    ///         line_number: 1,
+    ///         column: 10,
+    ///         kind: GameParseErrorKind::DuplicateColor { color: String::from("red") },
+    ///     }),
+    /// );
+    /// ```
+    pub fn parse(string: &str, duplicate_colors: DuplicateColors) -> Result<Self, GameParseError> {
+        let error = |byte_offset: usize, kind: GameParseErrorKind| GameParseError {
+            line_number: 1,
+            column: string[..byte_offset].chars().count() + 1,
+            kind,
+        };
         let mut result = NumberOfCubesOfEachColor::default();
-        let subsets = string.split(", ");
-        for subset in subsets {
-            let mut subset_splitted = subset.split_ascii_whitespace();
-            let number: usize = subset_splitted.next().unwrap_or("0").parse().unwrap_or(0);
-            let color = subset_splitted.next();
-            if let Some(color) = color {
-                match color {
-                    "red" => result.red = number,
-                    "blue" => result.blue = number,
-                    "green" => result.green = number,
-                    _ => {}
+        let mut byte_offset = 0;
+        for cubes in string.split(',') {
+            let cubes_byte_offset = byte_offset + (cubes.len() - cubes.trim_start().len());
+            byte_offset += cubes.len() + 1;
+            let cubes = cubes.trim();
+            let Some((count, color)) = cubes.split_once(char::is_whitespace) else {
+                return Err(error(
+                    cubes_byte_offset,
+                    GameParseErrorKind::InvalidCubes {
+                        cubes: String::from(cubes),
+                    },
+                ));
+            };
+            let color_byte_offset = cubes_byte_offset + (cubes.len() - color.trim_start().len());
+            let color = color.trim_start();
+            if color.contains(char::is_whitespace) {
+                return Err(error(
+                    cubes_byte_offset,
+                    GameParseErrorKind::InvalidCubes {
+                        cubes: String::from(cubes),
+                    },
+                ));
+            }
+            let count: usize = count.parse().map_err(|_| {
+                error(
+                    cubes_byte_offset,
+                    GameParseErrorKind::InvalidCount {
+                        count: String::from(count),
+                    },
+                )
+            })?;
+            match result.counts.get_mut(color) {
+                Some(_) if duplicate_colors == DuplicateColors::Reject => {
+                    return Err(error(
+                        color_byte_offset,
+                        GameParseErrorKind::DuplicateColor {
+                            color: String::from(color),
+                        },
+                    ));
+                }
+                Some(result_count) => {
+                    *result_count = result_count.checked_add(count).ok_or_else(|| {
+                        error(
+                            cubes_byte_offset,
+                            GameParseErrorKind::CountOverflow {
+                                color: String::from(color),
+                            },
+                        )
+                    })?;
+                }
+                None => {
+                    result.counts.insert(String::from(color), count);
                 }
             }
         }
@@ -56,6 +174,126 @@ impl FromStr for NumberOfCubesOfEachColor {
     }
 }
 
+/// How to handle a color appearing more than once in the same revealed subset.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum DuplicateColors {
+    /// `"3 red, 2 red"` reveals 5 red cubes.
+    #[default]
+    Sum,
+    Reject,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum GameParseErrorKind {
+    InvalidHeader,
+    InvalidCount { count: String },
+    InvalidCubes { cubes: String },
+    DuplicateColor { color: String },
+    CountOverflow { color: String },
+}
+
+impl GameParseErrorKind {
+    pub const fn message(&self) -> &'static str {
+        match self {
+            GameParseErrorKind::InvalidHeader => "Game should start with `Game <id>: `.",
+            GameParseErrorKind::InvalidCount { .. } => {
+                "Number of cubes should be a non-negative integer."
+            }
+            GameParseErrorKind::InvalidCubes { .. } => {
+                "Cubes should be formatted as `<count> <color>`."
+            }
+            GameParseErrorKind::DuplicateColor { .. } => {
+                "Color should appear at most once per revealed subset."
+            }
+            GameParseErrorKind::CountOverflow { .. } => {
+                "Summed number of cubes of a color should not overflow `usize`."
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GameParseError {
+    /// Line number (starting at 1) in the input.
+    pub line_number: usize,
+    /// Column (starting at 1, counted in characters) of the offending text in the line.
+    pub column: usize,
+    pub kind: GameParseErrorKind,
+}
+
+impl fmt::Display for GameParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "Line {}, column {}: {}",
+            self.line_number,
+            self.column,
+            self.kind.message()
+        )?;
+        match &self.kind {
+            GameParseErrorKind::InvalidHeader => Ok(()),
+            GameParseErrorKind::InvalidCount { count } => write!(formatter, " (`{}`)", count),
+            GameParseErrorKind::InvalidCubes { cubes } => write!(formatter, " (`{}`)", cubes),
+            GameParseErrorKind::DuplicateColor { color } => write!(formatter, " (`{}`)", color),
+            GameParseErrorKind::CountOverflow { color } => write!(formatter, " (`{}`)", color),
+        }
+    }
+}
+
+impl std::error::Error for GameParseError {}
+
+/// Cubes hidden in the bag, against which the revealed subsets are checked.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Bag {
+    pub cubes: NumberOfCubesOfEachColor,
+}
+
+impl Default for Bag {
+    /// The bag of the puzzle: 12 red cubes, 13 green cubes, and 14 blue cubes.
+    fn default() -> Self {
+        Bag {
+            cubes: NumberOfCubesOfEachColor::from([("red", 12), ("green", 13), ("blue", 14)]),
+        }
+    }
+}
+
+impl Bag {
+    pub fn can_reveal(&self, subset_of_cubes: &NumberOfCubesOfEachColor) -> bool {
+        subset_of_cubes.is_subset_of(&self.cubes)
+    }
+}
+
+/// Color of a revealed subset of cubes that has more cubes than the bag.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RevealViolation {
+    /// Index of the violating subset in [`Game::subsets_of_cubes`].
+    pub index_subset: usize,
+    pub color: String,
+    pub revealed_count: usize,
+    pub bag_count: usize,
+}
+
+impl RevealViolation {
+    /// Returns by how many cubes the revealed subset exceeded the bag.
+    pub const fn excess(&self) -> usize {
+        self.revealed_count - self.bag_count
+    }
+}
+
+impl fmt::Display for RevealViolation {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "reveal {} shows {} {} cubes, {} more than the {} in the bag",
+            self.index_subset + 1,
+            self.revealed_count,
+            self.color,
+            self.excess(),
+            self.bag_count
+        )
+    }
+}
+
 #[derive(Debug, Default, PartialEq)]
 pub struct Game {
     pub id: usize,
@@ -81,21 +319,9 @@ impl FromStr for Game {
     /// let expected_result = Game {
     ///     id: 1,
     ///     subsets_of_cubes: vec![
-    ///         NumberOfCubesOfEachColor {
-    ///             red: 4,
-    ///             green: 0,
-    ///             blue: 3,
-    ///         },
-    ///         NumberOfCubesOfEachColor {
-    ///             red: 1,
-    ///             green: 2,
-    ///             blue: 6,
-    ///         },
-    ///         NumberOfCubesOfEachColor {
-    ///             red: 0,
-    ///             green: 2,
-    ///             blue: 0,
-    ///         },
+    ///         NumberOfCubesOfEachColor::from([("red", 4), ("blue", 3)]),
+    ///         NumberOfCubesOfEachColor::from([("red", 1), ("green", 2), ("blue", 6)]),
+    ///         NumberOfCubesOfEachColor::from([("green", 2)]),
     ///     ],
     /// };
     /// let actual_result = Game::from_str(string).unwrap();
@@ -103,71 +329,210 @@ impl FromStr for Game {
     /// assert_eq!(actual_result, expected_result);
     /// ```
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let mut result = Game::default();
-        let mut parts = string.split(": ");
-        result.id = parts
-            .next()
-            .unwrap_or("Game 1")
+        Game::parse(string, DuplicateColors::default()).map_err(|error| error.kind.message())
+    }
+}
+
+impl Game {
+    /// Parses a game line, located on line 1 in case of error.
+    pub fn parse(line: &str, duplicate_colors: DuplicateColors) -> Result<Self, GameParseError> {
+        let invalid_header = GameParseError {
+            line_number: 1,
+            column: 1,
+            kind: GameParseErrorKind::InvalidHeader,
+        };
+        let (header, subsets) = line.split_once(':').ok_or(invalid_header.clone())?;
+        let id = header
+            .trim()
             .strip_prefix("Game ")
-            .unwrap_or("1")
-            .parse()
-            .unwrap_or(1);
-        result.subsets_of_cubes = parts
-            .next()
-            .unwrap_or("0 red, 0 green, 0 blue")
-            .split("; ")
-            .map(|string| {
-                NumberOfCubesOfEachColor::from_str(string)
-                    .unwrap_or(NumberOfCubesOfEachColor::default())
+            .and_then(|id| id.trim().parse().ok())
+            .ok_or(invalid_header)?;
+        let mut subsets_of_cubes = vec![];
+        let mut byte_offset = header.len() + 1;
+        for subset in subsets.split(';') {
+            let subset_column = line[..byte_offset].chars().count();
+            byte_offset += subset.len() + 1;
+            let subset_of_cubes = NumberOfCubesOfEachColor::parse(subset, duplicate_colors)
+                .map_err(|error| GameParseError {
+                    column: subset_column + error.column,
+                    ..error
+                })?;
+            subsets_of_cubes.push(subset_of_cubes);
+        }
+        Ok(Game {
+            id,
+            subsets_of_cubes,
+        })
+    }
+
+    pub fn is_possible(&self, bag: &Bag) -> bool {
+        self.subsets_of_cubes
+            .iter()
+            .all(|subset_of_cubes| bag.can_reveal(subset_of_cubes))
+    }
+
+    /// Returns every color of every revealed subset that exceeds the `bag`, in reveal order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_2::{Bag, Game, RevealViolation};
+    ///
+    /// let game = Game::from_str("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green").unwrap();
+    ///
+    /// assert_eq!(
+    ///     game.violations(&Bag::default()),
+    ///     vec![RevealViolation {
+    ///         index_subset: 0,
+    ///         color: String::from("red"),
+    ///         revealed_count: 20,
+    ///         bag_count: 12,
+    ///     }],
+    /// );
+    /// ```
+    pub fn violations(&self, bag: &Bag) -> Vec<RevealViolation> {
+        self.subsets_of_cubes
+            .iter()
+            .enumerate()
+            .flat_map(|(index_subset, subset_of_cubes)| {
+                subset_of_cubes
+                    .counts
+                    .iter()
+                    .filter_map(move |(color, &revealed_count)| {
+                        let bag_count = bag.cubes.get(color);
+                        if revealed_count > bag_count {
+                            Some(RevealViolation {
+                                index_subset,
+                                color: color.to_owned(),
+                                revealed_count,
+                                bag_count,
+                            })
+                        } else {
+                            None
+                        }
+                    })
             })
-            .collect::<Vec<NumberOfCubesOfEachColor>>();
-        Ok(result)
+            .collect()
+    }
+
+    /// Returns the violations of the first revealed subset that exceeds the `bag`.
+    pub fn first_violating_reveal(&self, bag: &Bag) -> Vec<RevealViolation> {
+        let violations = self.violations(bag);
+        let Some(index_subset) = violations.first().map(|violation| violation.index_subset) else {
+            return vec![];
+        };
+        violations
+            .into_iter()
+            .take_while(|violation| violation.index_subset == index_subset)
+            .collect()
+    }
+
+    /// Returns the fewest number of cubes of each color that makes the game possible.
+    pub fn minimum_set_of_cubes(&self) -> NumberOfCubesOfEachColor {
+        self.subsets_of_cubes.iter().fold(
+            NumberOfCubesOfEachColor::default(),
+            |accumulator, subset_of_cubes| accumulator.union(subset_of_cubes),
+        )
     }
 }
 
 pub fn part_1(input: &str) -> usize {
+    part_1_with_bag(input, &Bag::default())
+}
+
+pub fn part_1_with_bag(input: &str, bag: &Bag) -> usize {
     input
         .lines()
         .map(|line| Game::from_str(line).unwrap_or_default())
-        .filter(|game| {
-            game.subsets_of_cubes
-                .iter()
-                .all(|number_of_cubes_of_each_color| {
-                    number_of_cubes_of_each_color.red <= NUMBER_OF_RED_CUBES_IN_THE_BAG
-                        && number_of_cubes_of_each_color.blue <= NUMBER_OF_BLUE_CUBES_IN_THE_BAG
-                        && number_of_cubes_of_each_color.green <= NUMBER_OF_GREEN_CUBES_IN_THE_BAG
-                })
-        })
+        .filter(|game| game.is_possible(bag))
         .map(|game| game.id)
         .sum::<usize>()
 }
 
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GameFeasibility {
+    pub id: usize,
+    pub violations: Vec<RevealViolation>,
+}
+
+impl GameFeasibility {
+    pub fn is_possible(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+/// Parses every game of the `input`, failing on the first ill-formatted line.
+pub fn parse_games(
+    input: &str,
+    duplicate_colors: DuplicateColors,
+) -> Result<Vec<Game>, GameParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index_line, line)| {
+            Game::parse(line, duplicate_colors).map_err(|error| GameParseError {
+                line_number: index_line + 1,
+                ..error
+            })
+        })
+        .collect()
+}
+
+/// Parses every game of the `input` like [`parse_games`] and returns whether each of them
+/// is possible with the `bag`, failing on the first ill-formatted line.
+pub fn games_feasibility(input: &str, bag: &Bag) -> Result<Vec<GameFeasibility>, GameParseError> {
+    Ok(parse_games(input, DuplicateColors::default())?
+        .into_iter()
+        .map(|game| GameFeasibility {
+            id: game.id,
+            violations: game.violations(bag),
+        })
+        .collect())
+}
+
+/// Lists every impossible game of the `input` with all the reasons it is impossible,
+/// failing on the first ill-formatted line.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_2::{report_impossible_games, Bag};
+///
+/// let input = "Game 1: 3 blue, 4 red
+/// Game 4: 1 green, 3 red, 6 blue; 3 green, 15 blue, 14 red";
+///
+/// assert_eq!(
+///     report_impossible_games(input, &Bag::default()),
+///     Ok(String::from(
+///         "Game 4 is impossible:
+///   - reveal 2 shows 15 blue cubes, 1 more than the 14 in the bag
+///   - reveal 2 shows 14 red cubes, 2 more than the 12 in the bag
+/// ",
+///     )),
+/// );
+/// assert!(report_impossible_games("Game 3: 20 red, x blue", &Bag::default()).is_err());
+/// ```
+pub fn report_impossible_games(input: &str, bag: &Bag) -> Result<String, GameParseError> {
+    let mut result = String::new();
+    for game_feasibility in games_feasibility(input, bag)? {
+        if game_feasibility.is_possible() {
+            continue;
+        }
+        result += &format!("Game {} is impossible:\n", game_feasibility.id);
+        for violation in &game_feasibility.violations {
+            result += &format!("  - {}\n", violation);
+        }
+    }
+    Ok(result)
+}
+
 pub fn part_2(input: &str) -> usize {
     input
         .lines()
         .map(|line| {
             let game = Game::from_str(line).unwrap_or_default();
-            let mut maximum_number_of_cubes_of_each_color = NumberOfCubesOfEachColor {
-                red: 1,
-                green: 1,
-                blue: 1,
-            };
-            for number_of_cubes_of_each_color in game.subsets_of_cubes {
-                if number_of_cubes_of_each_color.red > maximum_number_of_cubes_of_each_color.red {
-                    maximum_number_of_cubes_of_each_color.red = number_of_cubes_of_each_color.red;
-                }
-                if number_of_cubes_of_each_color.green > maximum_number_of_cubes_of_each_color.green
-                {
-                    maximum_number_of_cubes_of_each_color.green =
-                        number_of_cubes_of_each_color.green;
-                }
-                if number_of_cubes_of_each_color.blue > maximum_number_of_cubes_of_each_color.blue {
-                    maximum_number_of_cubes_of_each_color.blue = number_of_cubes_of_each_color.blue;
-                }
-            }
-            maximum_number_of_cubes_of_each_color.red
-                * maximum_number_of_cubes_of_each_color.green
-                * maximum_number_of_cubes_of_each_color.blue
+            game.minimum_set_of_cubes().power()
         })
         .sum::<usize>()
 }
@@ -195,4 +560,279 @@ mod puzzle_2023_day_2_tests {
     fn test_part_2() {
         assert_eq!(part_2(include_str!("../input.txt")), 59795);
     }
+
+    mod feasibility {
+        use std::str::FromStr;
+
+        use crate::{
+            games_feasibility, report_impossible_games, Bag, Game, GameParseError,
+            GameParseErrorKind, RevealViolation,
+        };
+
+        #[test]
+        fn test_first_violating_reveal() {
+            let game = Game::from_str(
+                "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            )
+            .unwrap();
+            let violations = game.first_violating_reveal(&Bag::default());
+            assert_eq!(
+                violations,
+                vec![
+                    RevealViolation {
+                        index_subset: 2,
+                        color: String::from("blue"),
+                        revealed_count: 15,
+                        bag_count: 14,
+                    },
+                    RevealViolation {
+                        index_subset: 2,
+                        color: String::from("red"),
+                        revealed_count: 14,
+                        bag_count: 12,
+                    },
+                ]
+            );
+            assert_eq!(violations[1].excess(), 2);
+            assert!(Game::from_str("Game 1: 3 blue, 4 red")
+                .unwrap()
+                .first_violating_reveal(&Bag::default())
+                .is_empty());
+        }
+
+        #[test]
+        fn test_games_feasibility_example() {
+            let possible_games_ids =
+                games_feasibility(include_str!("../input_example_1.txt"), &Bag::default())
+                    .unwrap()
+                    .iter()
+                    .filter(|game_feasibility| game_feasibility.is_possible())
+                    .map(|game_feasibility| game_feasibility.id)
+                    .collect::<Vec<usize>>();
+            assert_eq!(possible_games_ids, vec![1, 2, 5]);
+        }
+
+        #[test]
+        fn test_report_impossible_games_example() {
+            assert_eq!(
+                report_impossible_games(include_str!("../input_example_1.txt"), &Bag::default()),
+                Ok(String::from(
+                    "\
+Game 3 is impossible:
+  - reveal 1 shows 20 red cubes, 8 more than the 12 in the bag
+Game 4 is impossible:
+  - reveal 3 shows 15 blue cubes, 1 more than the 14 in the bag
+  - reveal 3 shows 14 red cubes, 2 more than the 12 in the bag
+"
+                ))
+            );
+        }
+
+        #[test]
+        fn test_report_malformed_games() {
+            let input = "Game 1: 3 blue\nGame 3: 20 red, x blue";
+            assert_eq!(
+                report_impossible_games(input, &Bag::default()),
+                Err(GameParseError {
+                    line_number: 2,
+                    column: 17,
+                    kind: GameParseErrorKind::InvalidCount {
+                        count: String::from("x"),
+                    },
+                })
+            );
+        }
+
+        #[test]
+        fn test_unknown_color_exceeds_the_bag() {
+            let game = Game::from_str("Game 9: 1 yellow").unwrap();
+            assert_eq!(
+                game.violations(&Bag::default())[0].to_string(),
+                "reveal 1 shows 1 yellow cubes, 1 more than the 0 in the bag"
+            );
+        }
+    }
+
+    mod parsing {
+        use std::str::FromStr;
+
+        use crate::{
+            parse_games, DuplicateColors, Game, GameParseError, GameParseErrorKind,
+            NumberOfCubesOfEachColor,
+        };
+
+        #[test]
+        fn test_duplicate_colors_are_summed() {
+            let game = Game::from_str("Game 2: 3 red, 2 red; 1 blue, 1 blue, 1 blue").unwrap();
+            assert_eq!(
+                game.subsets_of_cubes,
+                vec![
+                    NumberOfCubesOfEachColor::from([("red", 5)]),
+                    NumberOfCubesOfEachColor::from([("blue", 3)]),
+                ]
+            );
+        }
+
+        #[test]
+        fn test_summed_counts_overflow() {
+            let error = Game::parse(
+                "Game 2: 18446744073709551615 red, 1 red",
+                DuplicateColors::Sum,
+            )
+            .unwrap_err();
+            assert_eq!(
+                error,
+                GameParseError {
+                    line_number: 1,
+                    column: 35,
+                    kind: GameParseErrorKind::CountOverflow {
+                        color: String::from("red"),
+                    },
+                }
+            );
+            assert_eq!(
+                error.to_string(),
+                "Line 1, column 35: Summed number of cubes of a color should not overflow `usize`. (`red`)"
+            );
+        }
+
+        #[test]
+        fn test_duplicate_colors_are_rejected() {
+            assert_eq!(
+                Game::parse("Game 2: 1 blue; 3 red, 2 red", DuplicateColors::Reject),
+                Err(GameParseError {
+                    line_number: 1,
+                    column: 26,
+                    kind: GameParseErrorKind::DuplicateColor {
+                        color: String::from("red"),
+                    },
+                })
+            );
+        }
+
+        #[test]
+        fn test_malformed_lines_are_located() {
+            let input = "\
+Game 1: 3 blue, 4 red
+Game 2: 1 blue, x green
+Game 3: 1 red";
+            let error = parse_games(input, DuplicateColors::Sum).unwrap_err();
+            assert_eq!(
+                error,
+                GameParseError {
+                    line_number: 2,
+                    column: 17,
+                    kind: GameParseErrorKind::InvalidCount {
+                        count: String::from("x"),
+                    },
+                }
+            );
+            assert_eq!(
+                error.to_string(),
+                "Line 2, column 17: Number of cubes should be a non-negative integer. (`x`)"
+            );
+        }
+
+        #[test]
+        fn test_malformed_lines() {
+            let malformed_lines = [
+                ("3 blue, 4 red", GameParseErrorKind::InvalidHeader, 1),
+                ("Game x: 3 blue", GameParseErrorKind::InvalidHeader, 1),
+                (
+                    "Game 1: -3 blue",
+                    GameParseErrorKind::InvalidCount {
+                        count: String::from("-3"),
+                    },
+                    9,
+                ),
+                (
+                    "Game 1: 3 blue; blue",
+                    GameParseErrorKind::InvalidCubes {
+                        cubes: String::from("blue"),
+                    },
+                    17,
+                ),
+                (
+                    "Game 1: 3 light blue",
+                    GameParseErrorKind::InvalidCubes {
+                        cubes: String::from("3 light blue"),
+                    },
+                    9,
+                ),
+                (
+                    "Game 1: 3 blue,, 2 red",
+                    GameParseErrorKind::InvalidCubes {
+                        cubes: String::new(),
+                    },
+                    16,
+                ),
+            ];
+            for (line, kind, column) in malformed_lines {
+                assert_eq!(
+                    Game::parse(line, DuplicateColors::Sum),
+                    Err(GameParseError {
+                        line_number: 1,
+                        column,
+                        kind,
+                    }),
+                    "{}",
+                    line
+                );
+            }
+        }
+
+        #[test]
+        fn test_inputs_parse() {
+            for input in [
+                include_str!("../input_example_1.txt"),
+                include_str!("../input.txt"),
+            ] {
+                assert!(parse_games(input, DuplicateColors::Reject).is_ok());
+            }
+        }
+    }
+
+    mod palettes {
+        use std::str::FromStr;
+
+        use crate::{part_1_with_bag, part_2, Bag, Game, NumberOfCubesOfEachColor};
+
+        const INPUT: &str = "\
+Game 1: 3 cyan, 2 magenta; 1 yellow
+Game 2: 5 cyan; 2 black, 1 yellow
+Game 3: 1 magenta, 1 red";
+
+        #[test]
+        fn test_unknown_colors_are_kept() {
+            let game = Game::from_str("Game 7: 2 black, 1 yellow").unwrap();
+            assert_eq!(
+                game.subsets_of_cubes,
+                vec![NumberOfCubesOfEachColor::from([
+                    ("black", 2),
+                    ("yellow", 1)
+                ])]
+            );
+        }
+
+        #[test]
+        fn test_part_1_with_custom_bag() {
+            let bag = Bag {
+                cubes: NumberOfCubesOfEachColor::from([("cyan", 4), ("magenta", 2), ("yellow", 1)]),
+            };
+            assert_eq!(part_1_with_bag(INPUT, &bag), 1);
+            assert_eq!(part_1_with_bag(INPUT, &Bag::default()), 0);
+        }
+
+        #[test]
+        fn test_part_2_with_custom_palette() {
+            assert_eq!(part_2(INPUT), 6 + 10 + 1);
+        }
+
+        #[test]
+        fn test_zero_cubes_count_like_absent_colors() {
+            assert_eq!(part_2("Game 1: 0 red, 3 blue"), 3);
+            assert_eq!(part_2("Game 1: 3 blue"), 3);
+            assert_eq!(part_2("Game 1: 0 red; 0 blue"), 1);
+        }
+    }
 }
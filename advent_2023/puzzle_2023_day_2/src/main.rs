@@ -1,7 +1,17 @@
-use puzzle_2023_day_2::{part_1, part_2};
+use puzzle_2023_day_2::{part_1, part_2, report_impossible_games, Bag};
 
 fn main() {
     let input = include_str!("../input.txt");
+    if std::env::args()
+        .skip(1)
+        .any(|argument| argument == "--report")
+    {
+        match report_impossible_games(input, &Bag::default()) {
+            Ok(report) => print!("{}", report),
+            Err(error) => eprintln!("{}", error),
+        }
+        return;
+    }
     println!("- Day 2 of 2023: Cube Conundrum -");
     println!("Answer Part 1: {}", part_1(input));
     println!("Answer Part 2: {}", part_2(input));
@@ -1,20 +1,32 @@
 use std::collections::HashMap;
+use std::str::FromStr;
 
-#[derive(Debug, PartialEq, Default, Clone, Copy)]
+/// Position of a cell in the schematic.
+///
+/// Columns are counted in [`char`], never in bytes, so that multi-byte symbols
+/// such as `€` only take one column.
+#[derive(Debug, PartialEq, Eq, Hash, Default, Clone, Copy)]
 pub struct GearPosition {
     pub index_line: usize,
     pub index_character: usize,
 }
 
+/// Number of the schematic, spanning the [`char`] columns `index_start..=index_end`.
 #[derive(Debug, PartialEq, Default, Clone, Copy)]
 pub struct NumberPosition {
+    pub index_line: usize,
     pub index_start: usize,
     pub index_end: usize,
     pub value: usize,
-    pub gear_position: GearPosition,
 }
 
-pub fn get_numbers_positions_from_line(line: &str) -> Vec<NumberPosition> {
+#[derive(Debug, PartialEq, Default, Clone, Copy)]
+pub struct SchematicSymbol {
+    pub character: char,
+    pub position: GearPosition,
+}
+
+pub fn get_numbers_positions_from_line(index_line: usize, line: &str) -> Vec<NumberPosition> {
     let mut result = vec![];
     let mut number_string = String::from("");
     let mut index_start = 0;
@@ -29,10 +41,10 @@ pub fn get_numbers_positions_from_line(line: &str) -> Vec<NumberPosition> {
         } else if !number_string.is_empty() {
             let value: usize = number_string.parse().expect("Should parse as a `usize`");
             result.push(NumberPosition {
+                index_line,
                 index_start,
                 index_end,
                 value,
-                gear_position: GearPosition::default(),
             });
             number_string = String::from("");
             index_start = 0;
@@ -42,10 +54,10 @@ pub fn get_numbers_positions_from_line(line: &str) -> Vec<NumberPosition> {
     if !number_string.is_empty() {
         let value: usize = number_string.parse().expect("Should parse as a `usize`");
         result.push(NumberPosition {
+            index_line,
             index_start,
             index_end,
             value,
-            gear_position: GearPosition::default(),
         });
     }
     result
@@ -59,145 +71,440 @@ pub fn is_gear_symbol(character: char) -> bool {
     character == '*'
 }
 
-pub fn part_1(input: &str) -> usize {
-    let lines = input.lines().collect::<Vec<&str>>();
-    lines
-        .iter()
-        .enumerate()
-        .map(|(index_line, &line)| {
-            get_numbers_positions_from_line(line)
-                .iter()
-                .filter(|&number_position| {
-                    let index_start = number_position.index_start.saturating_sub(1);
-                    let index_end = if number_position.index_end + 1 >= line.len() {
-                        line.len().saturating_sub(1)
-                    } else {
-                        number_position.index_end + 1
-                    };
-
-                    let has_symbol_on_the_left =
-                        line.chars().nth(index_start).is_some_and(is_symbol);
-                    let has_symbol_on_the_right =
-                        line.chars().nth(index_end).is_some_and(is_symbol);
-
-                    let has_symbol_on_the_top = lines
-                        .get(index_line.saturating_sub(1))
-                        .is_some_and(|&line| {
-                            line.get(index_start..=index_end)
-                                .is_some_and(|value| value.chars().any(is_symbol))
-                        });
-
-                    let has_symbol_on_the_bottom = lines.get(index_line + 1).is_some_and(|&line| {
-                        line.get(index_start..=index_end)
-                            .is_some_and(|value| value.chars().any(is_symbol))
-                    });
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PartsCount {
+    Exactly(usize),
+    AtLeast(usize),
+}
 
-                    has_symbol_on_the_left
-                        || has_symbol_on_the_right
-                        || has_symbol_on_the_top
-                        || has_symbol_on_the_bottom
-                })
-                .map(|number_position| number_position.value)
-                .sum::<usize>()
-        })
-        .sum()
+impl PartsCount {
+    pub const fn contains(&self, count: usize) -> bool {
+        match *self {
+            PartsCount::Exactly(expected) => count == expected,
+            PartsCount::AtLeast(minimum) => count >= minimum,
+        }
+    }
 }
 
-pub fn part_2(input: &str) -> usize {
-    let lines = input.lines().collect::<Vec<&str>>();
-    let mut number_positions = vec![];
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum GearAggregation {
+    #[default]
+    Product,
+    Sum,
+    Max,
+}
 
-    lines.iter().enumerate().for_each(|(index_line, &line)| {
-        get_numbers_positions_from_line(line)
-            .iter()
-            .for_each(|&number_position| {
-                let index_start = number_position.index_start.saturating_sub(1);
-                let index_end = if number_position.index_end + 1 >= line.len() {
-                    line.len().saturating_sub(1)
-                } else {
-                    number_position.index_end + 1
-                };
+impl GearAggregation {
+    pub fn aggregate(&self, values: &[usize]) -> usize {
+        match self {
+            GearAggregation::Product => values.iter().product(),
+            GearAggregation::Sum => values.iter().sum(),
+            GearAggregation::Max => values.iter().max().copied().unwrap_or_default(),
+        }
+    }
+}
 
-                let has_symbol_on_the_left =
-                    line.chars().nth(index_start).is_some_and(is_gear_symbol);
-                if has_symbol_on_the_left {
-                    let mut number_position = number_position.to_owned();
-                    number_position.gear_position.index_line = index_line;
-                    number_position.gear_position.index_character = index_start;
-                    number_positions.push(number_position);
-                }
+/// Describes which symbols are gears and how their adjacent part numbers are combined.
+///
+/// The default rule is the puzzle one: a `*` adjacent to exactly two part numbers,
+/// whose gear ratio is their product.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+/// use puzzle_2023_day_3::{EngineSchematic, GearAggregation, GearRule, PartsCount};
+///
+/// let schematic = EngineSchematic::from_str("1*2\n.#.\n3.4").unwrap();
+/// let gear_rule = GearRule {
+///     symbols: vec!['*', '#'],
+///     parts_count: PartsCount::AtLeast(2),
+///     aggregation: GearAggregation::Sum,
+/// };
+///
+/// assert_eq!(schematic.sum_of_gears(&GearRule::default()), 2);
+/// assert_eq!(schematic.sum_of_gears(&gear_rule), 3 + 10);
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct GearRule {
+    pub symbols: Vec<char>,
+    pub parts_count: PartsCount,
+    pub aggregation: GearAggregation,
+}
 
-                let has_symbol_on_the_right =
-                    line.chars().nth(index_end).is_some_and(is_gear_symbol);
-                if has_symbol_on_the_right {
-                    let mut number_position = number_position.to_owned();
-                    number_position.gear_position.index_line = index_line;
-                    number_position.gear_position.index_character = index_end;
-                    number_positions.push(number_position);
-                }
+impl Default for GearRule {
+    fn default() -> Self {
+        GearRule {
+            symbols: vec!['*'],
+            parts_count: PartsCount::Exactly(2),
+            aggregation: GearAggregation::Product,
+        }
+    }
+}
 
-                let index_character_top =
-                    lines.get(index_line.saturating_sub(1)).and_then(|&line| {
-                        line.get(index_start..=index_end)
-                            .and_then(|value| value.chars().position(is_gear_symbol))
+impl GearRule {
+    pub fn is_gear_symbol(&self, character: char) -> bool {
+        self.symbols.contains(&character)
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum SchematicCellKind {
+    #[default]
+    Empty,
+    PartNumber,
+    NonPartNumber,
+    Symbol,
+    Gear,
+}
+
+impl SchematicCellKind {
+    const fn ansi_color(&self) -> Option<&'static str> {
+        match self {
+            SchematicCellKind::Empty => None,
+            SchematicCellKind::PartNumber => Some("\x1b[32m"),
+            SchematicCellKind::NonPartNumber => Some("\x1b[31m"),
+            SchematicCellKind::Symbol => Some("\x1b[36m"),
+            SchematicCellKind::Gear => Some("\x1b[1;33m"),
+        }
+    }
+
+    const fn markers(&self) -> (&'static str, &'static str) {
+        match self {
+            SchematicCellKind::PartNumber => ("[", "]"),
+            SchematicCellKind::NonPartNumber => ("(", ")"),
+            SchematicCellKind::Gear => ("{", "}"),
+            SchematicCellKind::Empty | SchematicCellKind::Symbol => ("", ""),
+        }
+    }
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct SchematicAdjacency {
+    /// Positions of the symbols adjacent to each number, indexed like [`EngineSchematic::numbers`].
+    pub symbols_by_number: Vec<Vec<GearPosition>>,
+    /// Indexes in [`EngineSchematic::numbers`] of the numbers adjacent to each symbol.
+    pub numbers_by_symbol: HashMap<GearPosition, Vec<usize>>,
+}
+
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct EngineSchematic {
+    pub grid: Vec<Vec<char>>,
+    pub numbers: Vec<NumberPosition>,
+    pub symbols: Vec<SchematicSymbol>,
+}
+
+impl FromStr for EngineSchematic {
+    type Err = &'static str;
+
+    /// Parses a string `string` to return a value of [`EngineSchematic`]
+    ///
+    /// If parsing succeeds, return the value inside [`Ok`], otherwise
+    /// when the string is ill-formatted return an error specific to the
+    /// inside [`Err`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_3::{EngineSchematic, GearPosition, NumberPosition, SchematicSymbol};
+    ///
+    /// let string = "467..
+    /// ...*.";
+    /// let expected_result = EngineSchematic {
+    ///     grid: vec![
+    ///         vec!['4', '6', '7', '.', '.'],
+    ///         vec!['.', '.', '.', '*', '.'],
+    ///     ],
+    ///     numbers: vec![NumberPosition {
+    ///         index_line: 0,
+    ///         index_start: 0,
+    ///         index_end: 2,
+    ///         value: 467,
+    ///     }],
+    ///     symbols: vec![SchematicSymbol {
+    ///         character: '*',
+    ///         position: GearPosition {
+    ///             index_line: 1,
+    ///             index_character: 3,
+    ///         },
+    ///     }],
+    /// };
+    /// let actual_result = EngineSchematic::from_str(string).unwrap();
+    ///
+    /// assert_eq!(actual_result, expected_result);
+    /// ```
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut result = EngineSchematic::default();
+        for (index_line, line) in string.lines().enumerate() {
+            result
+                .numbers
+                .extend(get_numbers_positions_from_line(index_line, line));
+            for (index_character, character) in line.chars().enumerate() {
+                if is_symbol(character) {
+                    result.symbols.push(SchematicSymbol {
+                        character,
+                        position: GearPosition {
+                            index_line,
+                            index_character,
+                        },
                     });
-                if let Some(index_character_top) = index_character_top {
-                    let mut number_position = number_position.to_owned();
-                    number_position.gear_position.index_line = index_line.saturating_sub(1);
-                    number_position.gear_position.index_character =
-                        index_character_top + index_start;
-                    number_positions.push(number_position);
                 }
+            }
+            result.grid.push(line.chars().collect());
+        }
+        Ok(result)
+    }
+}
 
-                let index_character_bottom = lines.get(index_line + 1).and_then(|&line| {
-                    line.get(index_start..=index_end)
-                        .and_then(|value| value.chars().position(is_gear_symbol))
-                });
-                if let Some(index_character_bottom) = index_character_bottom {
-                    let mut number_position = number_position.to_owned();
-                    number_position.gear_position.index_line = index_line + 1;
-                    number_position.gear_position.index_character =
-                        index_character_bottom + index_start;
-                    number_positions.push(number_position);
+impl EngineSchematic {
+    pub fn get(&self, position: GearPosition) -> Option<char> {
+        self.grid
+            .get(position.index_line)
+            .and_then(|line| line.get(position.index_character))
+            .copied()
+    }
+
+    /// Returns the symbols adjacent to `number`, even diagonally, by only looking at
+    /// the cells surrounding it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_3::{EngineSchematic, GearPosition, SchematicSymbol};
+    ///
+    /// let schematic = EngineSchematic::from_str("467..
+    /// ...*.").unwrap();
+    ///
+    /// assert_eq!(
+    ///     schematic.adjacent_symbols(&schematic.numbers[0]),
+    ///     vec![SchematicSymbol {
+    ///         character: '*',
+    ///         position: GearPosition {
+    ///             index_line: 1,
+    ///             index_character: 3,
+    ///         },
+    ///     }],
+    /// );
+    /// ```
+    pub fn adjacent_symbols(&self, number: &NumberPosition) -> Vec<SchematicSymbol> {
+        let index_line_start = number.index_line.saturating_sub(1);
+        let index_line_end = number.index_line + 1;
+        let index_character_start = number.index_start.saturating_sub(1);
+        let index_character_end = number.index_end + 1;
+        let mut result = vec![];
+        for index_line in index_line_start..=index_line_end {
+            for index_character in index_character_start..=index_character_end {
+                let position = GearPosition {
+                    index_line,
+                    index_character,
+                };
+                if let Some(character) = self.get(position).filter(|&value| is_symbol(value)) {
+                    result.push(SchematicSymbol {
+                        character,
+                        position,
+                    });
                 }
-            });
-    });
-
-    // Key: "index_line-index_character"
-    // Value: usize
-    let mut gear_positions: HashMap<String, Vec<usize>> = HashMap::new();
-
-    number_positions.iter().for_each(|&number_position| {
-        let key = format!(
-            "{}-{}",
-            number_position.gear_position.index_line, number_position.gear_position.index_character,
-        );
-        match gear_positions.get_mut(&key) {
-            Some(gear_positions) => {
-                gear_positions.push(number_position.value);
-            }
-            None => {
-                gear_positions.insert(key, vec![number_position.value]);
             }
         }
-    });
+        result
+    }
 
-    let mut result = 0;
+    pub fn is_part_number(&self, number: &NumberPosition) -> bool {
+        !self.adjacent_symbols(number).is_empty()
+    }
 
-    for (_, value) in gear_positions.iter() {
-        if value.len() != 2 {
-            continue;
+    pub fn part_numbers(&self) -> impl Iterator<Item = &NumberPosition> {
+        self.numbers
+            .iter()
+            .filter(|number| self.is_part_number(number))
+    }
+
+    /// Returns the complete adjacency between numbers and symbols: a number touching
+    /// several symbols is linked to every one of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_3::{EngineSchematic, GearPosition};
+    ///
+    /// let schematic = EngineSchematic::from_str("7*..*8
+    /// ..45..").unwrap();
+    /// let adjacency = schematic.adjacency();
+    ///
+    /// assert_eq!(
+    ///     adjacency.symbols_by_number[2],
+    ///     vec![
+    ///         GearPosition { index_line: 0, index_character: 1 },
+    ///         GearPosition { index_line: 0, index_character: 4 },
+    ///     ],
+    /// );
+    /// assert_eq!(
+    ///     adjacency.numbers_by_symbol[&GearPosition { index_line: 0, index_character: 4 }],
+    ///     vec![1, 2],
+    /// );
+    /// ```
+    pub fn adjacency(&self) -> SchematicAdjacency {
+        let mut result = SchematicAdjacency {
+            symbols_by_number: Vec::with_capacity(self.numbers.len()),
+            numbers_by_symbol: self
+                .symbols
+                .iter()
+                .map(|symbol| (symbol.position, vec![]))
+                .collect(),
+        };
+        for (index_number, number) in self.numbers.iter().enumerate() {
+            let symbols_positions = self
+                .adjacent_symbols(number)
+                .iter()
+                .map(|symbol| symbol.position)
+                .collect::<Vec<GearPosition>>();
+            for symbol_position in &symbols_positions {
+                result
+                    .numbers_by_symbol
+                    .entry(*symbol_position)
+                    .or_default()
+                    .push(index_number);
+            }
+            result.symbols_by_number.push(symbols_positions);
         }
+        result
+    }
 
-        let first_number = value.first();
-        let second_number = value.last();
+    /// Returns the value, aggregated according to `gear_rule`, of every gear.
+    ///
+    /// Symbols without any adjacent part number are never gears.
+    pub fn gears(&self, gear_rule: &GearRule) -> HashMap<GearPosition, usize> {
+        let adjacency = self.adjacency();
+        self.symbols
+            .iter()
+            .filter(|symbol| gear_rule.is_gear_symbol(symbol.character))
+            .filter_map(|symbol| {
+                let indexes_numbers = adjacency.numbers_by_symbol.get(&symbol.position)?;
+                if indexes_numbers.is_empty()
+                    || !gear_rule.parts_count.contains(indexes_numbers.len())
+                {
+                    return None;
+                }
+                let values = indexes_numbers
+                    .iter()
+                    .map(|&index_number| self.numbers[index_number].value)
+                    .collect::<Vec<usize>>();
+                Some((symbol.position, gear_rule.aggregation.aggregate(&values)))
+            })
+            .collect()
+    }
+
+    pub fn sum_of_gears(&self, gear_rule: &GearRule) -> usize {
+        self.gears(gear_rule).values().sum()
+    }
+
+    /// Returns the gear ratio of every `*` symbol adjacent to exactly two part numbers.
+    pub fn gear_ratios(&self) -> HashMap<GearPosition, usize> {
+        self.gears(&GearRule::default())
+    }
 
-        if let (Some(first_number), Some(second_number)) = (first_number, second_number) {
-            result += first_number * second_number;
+    /// Classifies every cell of the grid, gears being the ones of `gear_rule`.
+    pub fn cells_kinds(&self, gear_rule: &GearRule) -> Vec<Vec<SchematicCellKind>> {
+        let mut result = self
+            .grid
+            .iter()
+            .map(|line| vec![SchematicCellKind::Empty; line.len()])
+            .collect::<Vec<Vec<SchematicCellKind>>>();
+        for number in &self.numbers {
+            let kind = if self.is_part_number(number) {
+                SchematicCellKind::PartNumber
+            } else {
+                SchematicCellKind::NonPartNumber
+            };
+            result[number.index_line][number.index_start..=number.index_end].fill(kind);
         }
+        let gears = self.gears(gear_rule);
+        for symbol in &self.symbols {
+            let position = symbol.position;
+            result[position.index_line][position.index_character] = if gears.contains_key(&position)
+            {
+                SchematicCellKind::Gear
+            } else {
+                SchematicCellKind::Symbol
+            };
+        }
+        result
     }
 
-    result
+    /// Renders the schematic with part numbers, non-part numbers, symbols and gears
+    /// (according to `gear_rule`) in distinct ANSI colors.
+    ///
+    /// When `is_colored` is `false`, markers are used instead: `[467]` for a part
+    /// number, `(114)` for a non-part number and `{*}` for a gear.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_3::{EngineSchematic, GearRule};
+    ///
+    /// let schematic = EngineSchematic::from_str("467..114\n...*....\n..35..#.").unwrap();
+    ///
+    /// assert_eq!(
+    ///     schematic.render(&GearRule::default(), false),
+    ///     "[467]..(114)\n...{*}....\n..[35]..#.\n",
+    /// );
+    /// ```
+    pub fn render(&self, gear_rule: &GearRule, is_colored: bool) -> String {
+        let cells_kinds = self.cells_kinds(gear_rule);
+        let mut result = String::new();
+        for (line, line_kinds) in self.grid.iter().zip(cells_kinds.iter()) {
+            let mut index_character = 0;
+            while index_character < line.len() {
+                let kind = line_kinds[index_character];
+                let mut index_end = index_character + 1;
+                let is_number = matches!(
+                    kind,
+                    SchematicCellKind::PartNumber | SchematicCellKind::NonPartNumber
+                );
+                while is_number && index_end < line.len() && line_kinds[index_end] == kind {
+                    index_end += 1;
+                }
+                let text = line[index_character..index_end].iter().collect::<String>();
+                match (is_colored, kind.ansi_color()) {
+                    (true, Some(color)) => {
+                        result += color;
+                        result += &text;
+                        result += ANSI_RESET;
+                    }
+                    (true, None) => result += &text,
+                    (false, _) => {
+                        let (opening_marker, closing_marker) = kind.markers();
+                        result += opening_marker;
+                        result += &text;
+                        result += closing_marker;
+                    }
+                }
+                index_character = index_end;
+            }
+            result += "\n";
+        }
+        result
+    }
+}
+
+pub fn part_1(input: &str) -> usize {
+    let schematic = EngineSchematic::from_str(input).unwrap_or_default();
+    schematic.part_numbers().map(|number| number.value).sum()
+}
+
+pub fn part_2(input: &str) -> usize {
+    part_2_with_gear_rule(input, &GearRule::default())
+}
+
+pub fn part_2_with_gear_rule(input: &str, gear_rule: &GearRule) -> usize {
+    let schematic = EngineSchematic::from_str(input).unwrap_or_default();
+    schematic.sum_of_gears(gear_rule)
 }
 
 #[cfg(test)]
@@ -223,4 +530,181 @@ mod puzzle_2023_day_3_tests {
     fn test_part_2() {
         assert_eq!(part_2(include_str!("../input.txt")), 84363105);
     }
+
+    mod gear_rules {
+        use crate::{part_2_with_gear_rule, GearAggregation, GearRule, PartsCount};
+
+        #[test]
+        fn test_default_gear_rule_example() {
+            assert_eq!(
+                part_2_with_gear_rule(include_str!("../input_example_1.txt"), &GearRule::default()),
+                467835
+            );
+        }
+
+        #[test]
+        fn test_minimum_parts_count_example() {
+            let gear_rule = GearRule {
+                parts_count: PartsCount::AtLeast(1),
+                ..GearRule::default()
+            };
+            assert_eq!(
+                part_2_with_gear_rule(include_str!("../input_example_1.txt"), &gear_rule),
+                467835 + 617
+            );
+        }
+
+        #[test]
+        fn test_symbols_and_aggregations_example() {
+            let gear_rule = GearRule {
+                symbols: vec!['*', '#', '+', '$'],
+                parts_count: PartsCount::Exactly(1),
+                aggregation: GearAggregation::Max,
+            };
+            assert_eq!(
+                part_2_with_gear_rule(include_str!("../input_example_1.txt"), &gear_rule),
+                617 + 633 + 592 + 664
+            );
+            let gear_rule = GearRule {
+                aggregation: GearAggregation::Sum,
+                ..GearRule::default()
+            };
+            assert_eq!(
+                part_2_with_gear_rule(include_str!("../input_example_1.txt"), &gear_rule),
+                467 + 35 + 755 + 598
+            );
+        }
+    }
+
+    mod render {
+        use std::str::FromStr;
+
+        use crate::{EngineSchematic, GearRule};
+
+        #[test]
+        fn test_render_plain_example() {
+            let schematic =
+                EngineSchematic::from_str(include_str!("../input_example_1.txt")).unwrap();
+            let expected = "\
+[467]..(114)..
+...{*}......
+..[35]..[633].
+......#...
+[617]*......
+.....+.(58).
+..[592].....
+......[755].
+...$.{*}....
+.[664].[598]..
+";
+            assert_eq!(schematic.render(&GearRule::default(), false), expected);
+        }
+
+        #[test]
+        fn test_render_colored() {
+            let schematic = EngineSchematic::from_str("1*2.3\n.+...").unwrap();
+            assert_eq!(
+                schematic.render(&GearRule::default(), true),
+                "\x1b[32m1\x1b[0m\x1b[1;33m*\x1b[0m\x1b[32m2\x1b[0m.\x1b[31m3\x1b[0m\n\
+                 .\x1b[36m+\x1b[0m...\n"
+            );
+        }
+    }
+
+    mod unicode {
+        use std::str::FromStr;
+
+        use crate::{part_1, part_2, EngineSchematic, GearPosition, GearRule, PartsCount};
+
+        #[test]
+        fn test_multi_byte_symbols_keep_char_columns() {
+            let schematic = EngineSchematic::from_str("€€12\n..§.\n→.34").unwrap();
+            assert_eq!(schematic.numbers[0].index_start, 2);
+            assert_eq!(schematic.numbers[0].index_end, 3);
+            assert_eq!(
+                schematic.symbols[2].position,
+                GearPosition {
+                    index_line: 1,
+                    index_character: 2,
+                }
+            );
+            assert_eq!(schematic.get(schematic.symbols[2].position), Some('§'));
+        }
+
+        #[test]
+        fn test_multi_byte_symbols_adjacency() {
+            let input = "é.....12\n......€.\n3→......";
+            assert_eq!(part_1(input), 12 + 3);
+            assert_eq!(part_1("€€€€€...\n......12"), 0);
+            assert_eq!(part_1("€€€€€€..\n......12"), 12);
+        }
+
+        #[test]
+        fn test_example_with_multi_byte_symbols() {
+            let input = include_str!("../input_example_1.txt")
+                .replace('*', "✱")
+                .replace('#', "€")
+                .replace('$', "§");
+            assert_eq!(part_1(&input), 4361);
+            let gear_rule = GearRule {
+                symbols: vec!['✱'],
+                parts_count: PartsCount::Exactly(2),
+                ..GearRule::default()
+            };
+            let schematic = EngineSchematic::from_str(&input).unwrap();
+            assert_eq!(schematic.sum_of_gears(&gear_rule), 467835);
+            assert_eq!(part_2(&input), 0);
+            assert!(schematic
+                .render(&gear_rule, false)
+                .starts_with("[467]..(114)..\n...{✱}......\n"));
+        }
+    }
+
+    mod engine_schematic {
+        use std::str::FromStr;
+
+        use crate::{part_2, EngineSchematic};
+
+        #[test]
+        fn test_part_numbers_example() {
+            let schematic =
+                EngineSchematic::from_str(include_str!("../input_example_1.txt")).unwrap();
+            let part_numbers = schematic
+                .part_numbers()
+                .map(|number| number.value)
+                .collect::<Vec<usize>>();
+            assert_eq!(part_numbers, vec![467, 35, 633, 617, 592, 755, 664, 598]);
+            assert_eq!(schematic.numbers.len(), 10);
+            assert_eq!(schematic.symbols.len(), 6);
+        }
+
+        #[test]
+        fn test_number_adjacent_to_two_gears_on_the_same_line() {
+            assert_eq!(part_2("7*..*8\n..45.."), 7 * 45 + 8 * 45);
+            assert_eq!(part_2("..45..\n7*..*8"), 7 * 45 + 8 * 45);
+        }
+
+        #[test]
+        fn test_gear_ratios_dense() {
+            let schematic = EngineSchematic::from_str("2*3\n*4*\n5*6").unwrap();
+            let gear_ratios = schematic.gear_ratios();
+            assert!(gear_ratios.is_empty());
+            let adjacency = schematic.adjacency();
+            assert!(adjacency
+                .symbols_by_number
+                .iter()
+                .all(|symbols_positions| !symbols_positions.is_empty()));
+            assert_eq!(adjacency.symbols_by_number[2].len(), 4);
+        }
+
+        #[test]
+        fn test_number_at_line_edges() {
+            let schematic = EngineSchematic::from_str("12.\n..#\n#.3").unwrap();
+            let part_numbers = schematic
+                .part_numbers()
+                .map(|number| number.value)
+                .collect::<Vec<usize>>();
+            assert_eq!(part_numbers, vec![12, 3]);
+        }
+    }
 }
@@ -1,7 +1,17 @@
-use puzzle_2023_day_3::{part_1, part_2};
+use std::str::FromStr;
+
+use puzzle_2023_day_3::{part_1, part_2, EngineSchematic, GearRule};
 
 fn main() {
     let input = include_str!("../input.txt");
+    let arguments = std::env::args().skip(1).collect::<Vec<String>>();
+    if arguments.iter().any(|argument| argument == "--render") {
+        let is_colored = !arguments.iter().any(|argument| argument == "--no-color")
+            && std::env::var_os("NO_COLOR").is_none();
+        let schematic = EngineSchematic::from_str(input).unwrap_or_default();
+        print!("{}", schematic.render(&GearRule::default(), is_colored));
+        return;
+    }
     println!("- Day 3 of 2023: Gear Ratios -");
     println!("Answer Part 1: {}", part_1(input));
     println!("Answer Part 2: {}", part_2(input));
@@ -1,4 +1,6 @@
-use std::cmp;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
 use std::str::FromStr;
 
 #[derive(Debug, Default, PartialEq, Clone)]
@@ -42,10 +44,54 @@ impl FromStr for CardNumbers {
     }
 }
 
+/// How an owned number appearing several times on a card is matched against the winning numbers.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum DuplicateNumbers {
+    /// Every occurrence of an owned winning number is a match.
+    #[default]
+    Count,
+
+    /// An owned winning number is a match only once, however many times it appears.
+    Ignore,
+}
+
 #[derive(Debug, Default, PartialEq, Clone)]
 pub struct Card {
     pub id: usize,
-    pub winning_numbers_count: usize,
+    pub winning_numbers: CardNumbers,
+    pub owned_numbers: CardNumbers,
+}
+
+impl Card {
+    /// Returns the owned numbers that are winning numbers, in the order they appear on the card.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_4::{Card, DuplicateNumbers};
+    ///
+    /// let card = Card::from_str("Card 1: 41 48 83 | 83 86 48 83 17").unwrap();
+    ///
+    /// assert_eq!(card.matching_numbers(DuplicateNumbers::Count), vec![83, 48, 83]);
+    /// assert_eq!(card.matching_numbers(DuplicateNumbers::Ignore), vec![83, 48]);
+    /// ```
+    pub fn matching_numbers(&self, duplicate_numbers: DuplicateNumbers) -> Vec<u32> {
+        let mut result: Vec<u32> = Vec::new();
+        for owned_number in &self.owned_numbers.numbers {
+            let is_duplicate =
+                duplicate_numbers == DuplicateNumbers::Ignore && result.contains(owned_number);
+            if self.winning_numbers.numbers.contains(owned_number) && !is_duplicate {
+                result.push(*owned_number);
+            }
+        }
+        result
+    }
+
+    /// Returns the number of owned numbers that are winning numbers, duplicates counted.
+    pub fn winning_numbers_count(&self) -> usize {
+        self.matching_numbers(DuplicateNumbers::Count).len()
+    }
 }
 
 impl FromStr for Card {
@@ -66,85 +112,572 @@ impl FromStr for Card {
     /// let string = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53";
     /// let expected_result = Card {
     ///     id: 1,
-    ///     winning_numbers_count: 4,
+    ///     winning_numbers: CardNumbers {
+    ///         numbers: vec![41, 48, 83, 86, 17],
+    ///     },
+    ///     owned_numbers: CardNumbers {
+    ///         numbers: vec![83, 86, 6, 31, 17, 9, 48, 53],
+    ///     },
     /// };
     /// let actual_result = Card::from_str(string).unwrap();
     ///
     /// assert_eq!(actual_result, expected_result);
+    /// assert_eq!(actual_result.winning_numbers_count(), 4);
     /// ```
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let mut result = Card::default();
-        let mut parts = string.split(": ");
-        result.id = parts
-            .next()
-            .unwrap_or("Card 1")
-            .strip_prefix("Card ")
-            .unwrap_or("1")
-            .trim()
-            .parse()
-            .unwrap_or(1);
-
-        let mut numbers_parts = parts.next().unwrap_or("").split(" | ");
-
-        let winning_numbers =
-            CardNumbers::from_str(numbers_parts.next().unwrap_or("")).unwrap_or_default();
-        let owned_numbers =
-            CardNumbers::from_str(numbers_parts.next().unwrap_or("")).unwrap_or_default();
-
-        result.winning_numbers_count = owned_numbers
-            .numbers
+        let mut numbers_parts = string.split(": ").nth(1).unwrap_or("").split(" | ");
+        let result = Card {
+            id: parse_card_id(string).ok_or("Card should start with `Card <id>: `.")?,
+            winning_numbers: CardNumbers::from_str(numbers_parts.next().unwrap_or(""))
+                .unwrap_or_default(),
+            owned_numbers: CardNumbers::from_str(numbers_parts.next().unwrap_or(""))
+                .unwrap_or_default(),
+        };
+        Ok(result)
+    }
+}
+
+/// Parses the identifier of the card `line` from its `Card <id>: ` header.
+fn parse_card_id(line: &str) -> Option<usize> {
+    line.split(": ")
+        .next()?
+        .strip_prefix("Card ")?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Error of a deck with a card that cannot be identified.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum DeckParseError {
+    /// The card does not start with a `Card <id>: ` header.
+    InvalidHeader {
+        /// Line number (starting at 1) of the card.
+        line_number: usize,
+        line: String,
+    },
+
+    /// Two cards share the same identifier.
+    DuplicateId {
+        id: usize,
+
+        /// Line number (starting at 1) of the first card with the identifier.
+        first_line_number: usize,
+
+        /// Line number (starting at 1) of the card repeating the identifier.
+        line_number: usize,
+    },
+}
+
+impl fmt::Display for DeckParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DeckParseError::InvalidHeader { line_number, line } => write!(
+                formatter,
+                "Line {}: card should start with `Card <id>: ` (`{}`).",
+                line_number, line
+            ),
+            DeckParseError::DuplicateId {
+                id,
+                first_line_number,
+                line_number,
+            } => write!(
+                formatter,
+                "Line {}: card {} is already on line {}.",
+                line_number, id, first_line_number
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DeckParseError {}
+
+/// Parses the cards of the `input` and returns them sorted by identifier, so that the
+/// copies are won in the order of the identifiers whatever the order of the lines.
+///
+/// Identifiers do not have to be contiguous, nor start at 1.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_4::{parse_deck, DeckParseError};
+///
+/// let deck = parse_deck("Card 7: 1 | 1\nCard 0: 2 | 3").unwrap();
+/// assert_eq!(deck.iter().map(|card| card.id).collect::<Vec<usize>>(), vec![0, 7]);
+///
+/// assert_eq!(
+///     parse_deck("Card 7: 1 | 1\nCard 0: 2 | 3\nCard 7: 4 | 5"),
+///     Err(DeckParseError::DuplicateId {
+///         id: 7,
+///         first_line_number: 1,
+///         line_number: 3,
+///     }),
+/// );
+/// assert_eq!(
+///     parse_deck("Card 1: 1 | 1\nCard x: 2 | 2"),
+///     Err(DeckParseError::InvalidHeader {
+///         line_number: 2,
+///         line: String::from("Card x: 2 | 2"),
+///     }),
+/// );
+/// ```
+pub fn parse_deck(input: &str) -> Result<Vec<Card>, DeckParseError> {
+    let mut lines_numbers_by_id: HashMap<usize, usize> = HashMap::new();
+    let mut result = Vec::new();
+    for (index_line, line) in input.lines().enumerate() {
+        let line_number = index_line + 1;
+        let card = Card::from_str(line).map_err(|_| DeckParseError::InvalidHeader {
+            line_number,
+            line: String::from(line),
+        })?;
+        if let Some(&first_line_number) = lines_numbers_by_id.get(&card.id) {
+            return Err(DeckParseError::DuplicateId {
+                id: card.id,
+                first_line_number,
+                line_number,
+            });
+        }
+        lines_numbers_by_id.insert(card.id, line_number);
+        result.push(card);
+    }
+    result.sort_by_key(|card| card.id);
+    Ok(result)
+}
+
+/// Rule scoring a deck of scratchcards from the number of winning numbers of each of its cards.
+pub trait ScoringRule {
+    /// Returns the score of the deck, `winning_numbers_counts` being given in the deck order.
+    fn score(&self, winning_numbers_counts: &[usize]) -> usize;
+}
+
+/// Rules where each card is worth points on its own, the score being the sum of the points.
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum PointsRule {
+    /// The first match makes the card worth one point and each match after doubles it (part 1).
+    #[default]
+    Doubling,
+
+    /// Each match makes the card worth one more point.
+    Linear,
+
+    /// A card with `n` matches is worth the `n`-th Fibonacci number of points (1, 1, 2, 3, 5, ...).
+    Fibonacci,
+}
+
+impl PointsRule {
+    /// Returns the points a card with `winning_numbers_count` matches is worth.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_4::PointsRule;
+    ///
+    /// assert_eq!(PointsRule::Doubling.points(4), 8);
+    /// assert_eq!(PointsRule::Linear.points(4), 4);
+    /// assert_eq!(PointsRule::Fibonacci.points(4), 3);
+    /// assert_eq!(PointsRule::Fibonacci.points(0), 0);
+    /// ```
+    pub fn points(&self, winning_numbers_count: usize) -> usize {
+        match self {
+            PointsRule::Doubling => match winning_numbers_count {
+                0 => 0,
+                count => 2_usize.saturating_pow((count - 1) as u32),
+            },
+            PointsRule::Linear => winning_numbers_count,
+            PointsRule::Fibonacci => {
+                let (mut current, mut next): (usize, usize) = (0, 1);
+                for _ in 0..winning_numbers_count {
+                    (current, next) = (next, current.saturating_add(next));
+                }
+                current
+            }
+        }
+    }
+}
+
+impl ScoringRule for PointsRule {
+    fn score(&self, winning_numbers_counts: &[usize]) -> usize {
+        winning_numbers_counts
             .iter()
-            .filter(|&owned_number| winning_numbers.numbers.contains(owned_number))
-            .count();
+            .map(|&winning_numbers_count| self.points(winning_numbers_count))
+            .sum()
+    }
+}
 
-        Ok(result)
+/// Rules where cards win copies of other cards of the deck, the score being the total
+/// number of cards (originals and copies).
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum CopiesRule {
+    /// A card with `n` matches wins a copy of each of the `n` cards after it (part 2).
+    #[default]
+    NextCards,
+
+    /// A card with `n` matches wins a copy of each of the `n` cards before it.
+    PreviousCards,
+
+    /// A card with `n` matches wins a copy of each of the `min(n, cap)` cards after it.
+    CappedNextCards(usize),
+}
+
+impl CopiesRule {
+    /// Returns the indices in the deck of the cards won by the card at `index_card`
+    /// with `winning_numbers_count` matches, possibly past the end of the deck.
+    pub fn won_cards(&self, index_card: usize, winning_numbers_count: usize) -> Range<usize> {
+        match *self {
+            CopiesRule::NextCards => index_card + 1..index_card + 1 + winning_numbers_count,
+            CopiesRule::PreviousCards => {
+                index_card.saturating_sub(winning_numbers_count)..index_card
+            }
+            CopiesRule::CappedNextCards(cap) => {
+                index_card + 1..index_card + 1 + winning_numbers_count.min(cap)
+            }
+        }
     }
 }
 
-pub fn part_1(input: &str) -> usize {
-    input
-        .lines()
-        .map(|line| Card::from_str(line).unwrap_or_default())
-        .map(|card| {
-            let winning_numbers_count = card.winning_numbers_count as u32;
-            let base: usize = 2;
-            if winning_numbers_count > 0 {
-                base.pow(winning_numbers_count.saturating_sub(1))
-            } else {
-                0
+impl ScoringRule for CopiesRule {
+    fn score(&self, winning_numbers_counts: &[usize]) -> usize {
+        cards_instances(
+            winning_numbers_counts,
+            |index_card, winning_numbers_count| self.won_cards(index_card, winning_numbers_count),
+        )
+        .iter()
+        .fold(0, |total, &instances| total.saturating_add(instances))
+    }
+}
+
+/// Propagates the copies won through the deck and returns the number of instances
+/// (original and copies) of each card, in the deck order.
+///
+/// `won_cards` returns the indices of the cards won by the card at a given index with
+/// a given number of matches, indices past the end of the deck being ignored.
+///
+/// # Panics
+///
+/// Panics if a card wins, directly or through its copies, a copy of itself.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_4::cards_instances;
+///
+/// let instances = cards_instances(&[4, 2, 2, 1, 0, 0], |index_card, winning_numbers_count| {
+///     index_card + 1..index_card + 1 + winning_numbers_count
+/// });
+///
+/// assert_eq!(instances, vec![1, 2, 4, 8, 14, 1]);
+/// ```
+pub fn cards_instances(
+    winning_numbers_counts: &[usize],
+    won_cards: impl Fn(usize, usize) -> Range<usize>,
+) -> Vec<usize> {
+    instances_from_winners(&cards_winners(winning_numbers_counts, won_cards))
+}
+
+/// Returns, for each card of the deck, the indices of the cards winning a copy of it.
+fn cards_winners(
+    winning_numbers_counts: &[usize],
+    won_cards: impl Fn(usize, usize) -> Range<usize>,
+) -> Vec<Vec<usize>> {
+    let cards_count = winning_numbers_counts.len();
+    let mut winners: Vec<Vec<usize>> = vec![Vec::new(); cards_count];
+    for (index_card, &winning_numbers_count) in winning_numbers_counts.iter().enumerate() {
+        let won_cards = won_cards(index_card, winning_numbers_count);
+        let index_end = won_cards.end.min(cards_count);
+        for won_card_winners in &mut winners[won_cards.start.min(index_end)..index_end] {
+            won_card_winners.push(index_card);
+        }
+    }
+    winners
+}
+
+fn instances_from_winners(winners: &[Vec<usize>]) -> Vec<usize> {
+    let cards_count = winners.len();
+    let mut instances: Vec<Option<usize>> = vec![None; cards_count];
+    let mut is_visiting = vec![false; cards_count];
+    for index_card in 0..cards_count {
+        let mut stack = vec![index_card];
+        while let Some(&index_current) = stack.last() {
+            if instances[index_current].is_some() {
+                stack.pop();
+                continue;
             }
+            is_visiting[index_current] = true;
+            let pending_winner = winners[index_current]
+                .iter()
+                .find(|&&index_winner| instances[index_winner].is_none());
+            match pending_winner {
+                Some(&index_winner) => {
+                    assert!(
+                        !is_visiting[index_winner],
+                        "Card at index {index_winner} wins a copy of itself."
+                    );
+                    stack.push(index_winner);
+                }
+                None => {
+                    instances[index_current] = Some(
+                        winners[index_current]
+                            .iter()
+                            .map(|&index_winner| instances[index_winner].unwrap_or_default())
+                            .fold(1, usize::saturating_add),
+                    );
+                    is_visiting[index_current] = false;
+                    stack.pop();
+                }
+            }
+        }
+    }
+    instances
+        .into_iter()
+        .map(Option::unwrap_or_default)
+        .collect()
+}
+
+/// Origin of the instances of a card once the copies are propagated through the deck.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct CardProvenance {
+    pub id: usize,
+
+    /// Number of instances of the card, the original included.
+    pub instances: usize,
+
+    /// Identifiers of the cards that won copies of this card, with the number of copies won by each.
+    pub copies_from: Vec<(usize, usize)>,
+}
+
+impl CardProvenance {
+    /// Returns the number of copies received by the card, the original excluded.
+    pub fn copies(&self) -> usize {
+        self.instances.saturating_sub(1)
+    }
+}
+
+/// Parses the cards of the `input` and returns, for each of them in the order of the
+/// identifiers, how many copies it received with the `copies_rule` and from which cards,
+/// owned numbers being matched according to `duplicate_numbers`.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_4::{cards_provenance, CardProvenance, CopiesRule, DuplicateNumbers};
+///
+/// let input = "Card 1: 41 48 | 41 48\nCard 2: 17 | 17\nCard 3: 6 | 7";
+/// let provenance =
+///     cards_provenance(input, CopiesRule::NextCards, DuplicateNumbers::Count).unwrap();
+///
+/// assert_eq!(
+///     provenance[2],
+///     CardProvenance {
+///         id: 3,
+///         instances: 4,
+///         copies_from: vec![(1, 1), (2, 2)],
+///     },
+/// );
+/// ```
+pub fn cards_provenance(
+    input: &str,
+    copies_rule: CopiesRule,
+    duplicate_numbers: DuplicateNumbers,
+) -> Result<Vec<CardProvenance>, DeckParseError> {
+    Ok(deck_provenance(
+        &parse_deck(input)?,
+        copies_rule,
+        duplicate_numbers,
+    ))
+}
+
+/// Returns, for each card of the deck `cards` in the deck order, how many copies it received
+/// with the `copies_rule` and from which cards, owned numbers being matched according to
+/// `duplicate_numbers`.
+pub fn deck_provenance(
+    cards: &[Card],
+    copies_rule: CopiesRule,
+    duplicate_numbers: DuplicateNumbers,
+) -> Vec<CardProvenance> {
+    let winning_numbers_counts = winning_numbers_counts(cards, duplicate_numbers);
+    let winners = cards_winners(
+        &winning_numbers_counts,
+        |index_card, winning_numbers_count| {
+            copies_rule.won_cards(index_card, winning_numbers_count)
+        },
+    );
+    let instances = instances_from_winners(&winners);
+    cards
+        .iter()
+        .zip(&winners)
+        .zip(&instances)
+        .map(|((card, card_winners), &card_instances)| CardProvenance {
+            id: card.id,
+            instances: card_instances,
+            copies_from: card_winners
+                .iter()
+                .map(|&index_winner| (cards[index_winner].id, instances[index_winner]))
+                .collect(),
         })
-        .sum::<usize>()
+        .collect()
 }
 
-pub fn part_2(input: &str) -> usize {
-    let cards = input
-        .lines()
-        .map(|line| Card::from_str(line).unwrap_or_default())
-        .collect::<Vec<Card>>();
-    let mut cards_identifiers = cards.iter().map(|card| card.id).collect::<Vec<usize>>();
-
-    let mut index_card = 0;
-    loop {
-        if index_card >= cards_identifiers.len() {
-            break;
-        }
-
-        if let Some(card) = cards.get(cards_identifiers.get(index_card).unwrap_or(&0) - 1) {
-            let card_position_index = card.id - 1;
-            let maximum_index = cmp::min(
-                card_position_index + card.winning_numbers_count,
-                cards.len() - 1,
-            );
-            for index in card_position_index + 1..=maximum_index {
-                cards_identifiers.push(index + 1);
-            }
+fn pluralize(count: usize, word: &str) -> String {
+    match (count, word) {
+        (1, _) => format!("{count} {word}"),
+        (_, "copy") => format!("{count} copies"),
+        _ => format!("{count} {word}s"),
+    }
+}
+
+/// Renders the `provenance` of the cards as a tree, each card branching to the cards it
+/// received copies from, followed by the total number of cards.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_4::{cards_provenance, render_provenance_tree, CopiesRule, DuplicateNumbers};
+///
+/// let input = "Card 1: 41 48 | 41 48\nCard 2: 17 | 17\nCard 3: 6 | 7";
+/// let provenance =
+///     cards_provenance(input, CopiesRule::NextCards, DuplicateNumbers::Count).unwrap();
+///
+/// assert_eq!(
+///     render_provenance_tree(&provenance),
+///     "\
+/// Card 1: 1 instance
+/// Card 2: 2 instances (1 copy)
+/// └── 1 copy from Card 1
+/// Card 3: 4 instances (3 copies)
+/// ├── 1 copy from Card 1
+/// └── 2 copies from Card 2
+/// Total: 7 cards
+/// ",
+/// );
+/// ```
+pub fn render_provenance_tree(provenance: &[CardProvenance]) -> String {
+    let mut result = String::new();
+    for card_provenance in provenance {
+        result += &format!(
+            "Card {}: {}",
+            card_provenance.id,
+            pluralize(card_provenance.instances, "instance")
+        );
+        if card_provenance.copies() > 0 {
+            result += &format!(" ({})", pluralize(card_provenance.copies(), "copy"));
         }
+        result += "\n";
+        for (index_origin, &(id_origin, copies)) in card_provenance.copies_from.iter().enumerate() {
+            let branch = if index_origin + 1 == card_provenance.copies_from.len() {
+                "└──"
+            } else {
+                "├──"
+            };
+            result += &format!(
+                "{branch} {} from Card {id_origin}\n",
+                pluralize(copies, "copy")
+            );
+        }
+    }
+    let total = provenance.iter().fold(0_usize, |total, card_provenance| {
+        total.saturating_add(card_provenance.instances)
+    });
+    result += &format!("Total: {}\n", pluralize(total, "card"));
+    result
+}
 
-        index_card += 1;
+/// Renders the `provenance` of the cards as a DOT graph, with an edge from each card to
+/// the cards it won copies of, labeled with the number of copies.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_4::{cards_provenance, render_provenance_dot, CopiesRule, DuplicateNumbers};
+///
+/// let provenance = cards_provenance(
+///     "Card 1: 41 | 41\nCard 2: 17 | 6",
+///     CopiesRule::NextCards,
+///     DuplicateNumbers::Count,
+/// ).unwrap();
+///
+/// assert_eq!(
+///     render_provenance_dot(&provenance),
+///     "\
+/// digraph scratchcards {
+///     card_1 [label=\"Card 1\\n1 instance\"];
+///     card_2 [label=\"Card 2\\n2 instances\"];
+///     card_1 -> card_2 [label=\"1\"];
+/// }
+/// ",
+/// );
+/// ```
+pub fn render_provenance_dot(provenance: &[CardProvenance]) -> String {
+    let mut result = String::from("digraph scratchcards {\n");
+    for card_provenance in provenance {
+        result += &format!(
+            "    card_{} [label=\"Card {}\\n{}\"];\n",
+            card_provenance.id,
+            card_provenance.id,
+            pluralize(card_provenance.instances, "instance")
+        );
     }
+    for card_provenance in provenance {
+        for &(id_origin, copies) in &card_provenance.copies_from {
+            result += &format!(
+                "    card_{id_origin} -> card_{} [label=\"{copies}\"];\n",
+                card_provenance.id
+            );
+        }
+    }
+    result += "}\n";
+    result
+}
+
+/// Returns the number of winning numbers of each card of the deck `cards`, in the deck order,
+/// owned numbers being matched according to `duplicate_numbers`.
+fn winning_numbers_counts(cards: &[Card], duplicate_numbers: DuplicateNumbers) -> Vec<usize> {
+    cards
+        .iter()
+        .map(|card| card.matching_numbers(duplicate_numbers).len())
+        .collect()
+}
+
+/// Parses the cards of the `input` and returns the score of the deck, sorted by identifier,
+/// with the `scoring_rule`, owned numbers being matched according to `duplicate_numbers`.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_4::{score_cards, DuplicateNumbers, PointsRule};
+///
+/// let input = "Card 1: 1 2 | 1 1 2";
+///
+/// assert_eq!(score_cards(input, &PointsRule::Linear, DuplicateNumbers::Count), Ok(3));
+/// assert_eq!(score_cards(input, &PointsRule::Linear, DuplicateNumbers::Ignore), Ok(2));
+/// ```
+pub fn score_cards(
+    input: &str,
+    scoring_rule: &impl ScoringRule,
+    duplicate_numbers: DuplicateNumbers,
+) -> Result<usize, DeckParseError> {
+    Ok(score_deck(
+        &parse_deck(input)?,
+        scoring_rule,
+        duplicate_numbers,
+    ))
+}
 
-    cards_identifiers.len()
+/// Returns the score of the deck `cards`, in the deck order, with the `scoring_rule`, owned
+/// numbers being matched according to `duplicate_numbers`.
+pub fn score_deck(
+    cards: &[Card],
+    scoring_rule: &impl ScoringRule,
+    duplicate_numbers: DuplicateNumbers,
+) -> usize {
+    scoring_rule.score(&winning_numbers_counts(cards, duplicate_numbers))
+}
+
+pub fn part_1(input: &str) -> usize {
+    score_cards(input, &PointsRule::Doubling, DuplicateNumbers::default()).unwrap_or_default()
+}
+
+pub fn part_2(input: &str) -> usize {
+    score_cards(input, &CopiesRule::NextCards, DuplicateNumbers::default()).unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -170,4 +703,379 @@ mod puzzle_2023_day_4_tests {
     fn test_part_2() {
         assert_eq!(part_2(include_str!("../input.txt")), 5659035);
     }
+
+    mod matching_numbers {
+        use crate::{Card, DuplicateNumbers};
+        use std::str::FromStr;
+
+        #[test]
+        fn test_matching_numbers_example() {
+            let cards = include_str!("../input_example_1.txt")
+                .lines()
+                .map(|line| Card::from_str(line).unwrap())
+                .collect::<Vec<Card>>();
+            assert_eq!(
+                cards[0].matching_numbers(DuplicateNumbers::Count),
+                vec![83, 86, 17, 48]
+            );
+            assert_eq!(
+                cards[2].matching_numbers(DuplicateNumbers::Count),
+                vec![21, 1]
+            );
+            assert!(cards[4]
+                .matching_numbers(DuplicateNumbers::Count)
+                .is_empty());
+            for card in &cards {
+                assert_eq!(
+                    card.matching_numbers(DuplicateNumbers::Count),
+                    card.matching_numbers(DuplicateNumbers::Ignore)
+                );
+            }
+        }
+
+        #[test]
+        fn test_duplicate_owned_numbers() {
+            let card = Card::from_str("Card 7: 1 2 3 | 3 3 3 4 1 1").unwrap();
+            assert_eq!(card.id, 7);
+            assert_eq!(card.owned_numbers.numbers, vec![3, 3, 3, 4, 1, 1]);
+            assert_eq!(card.winning_numbers_count(), 5);
+            assert_eq!(card.matching_numbers(DuplicateNumbers::Ignore), vec![3, 1]);
+        }
+    }
+
+    mod scoring_rules {
+        use crate::{
+            cards_instances, score_cards, CopiesRule, DuplicateNumbers, PointsRule, ScoringRule,
+        };
+
+        #[test]
+        fn test_points_rules_example() {
+            let input = include_str!("../input_example_1.txt");
+            assert_eq!(
+                score_cards(input, &PointsRule::Doubling, DuplicateNumbers::Count).unwrap(),
+                13
+            );
+            assert_eq!(
+                score_cards(input, &PointsRule::Linear, DuplicateNumbers::Count).unwrap(),
+                9
+            );
+            assert_eq!(
+                score_cards(input, &PointsRule::Fibonacci, DuplicateNumbers::Count).unwrap(),
+                6
+            );
+        }
+
+        #[test]
+        fn test_copies_rules_example() {
+            let input = include_str!("../input_example_1.txt");
+            assert_eq!(
+                score_cards(input, &CopiesRule::NextCards, DuplicateNumbers::Count).unwrap(),
+                30
+            );
+            assert_eq!(
+                score_cards(input, &CopiesRule::PreviousCards, DuplicateNumbers::Count).unwrap(),
+                14
+            );
+            assert_eq!(
+                score_cards(
+                    input,
+                    &CopiesRule::CappedNextCards(1),
+                    DuplicateNumbers::Count
+                )
+                .unwrap(),
+                16
+            );
+            assert_eq!(
+                score_cards(
+                    input,
+                    &CopiesRule::CappedNextCards(4),
+                    DuplicateNumbers::Count
+                )
+                .unwrap(),
+                30
+            );
+            assert_eq!(
+                score_cards(
+                    input,
+                    &CopiesRule::CappedNextCards(0),
+                    DuplicateNumbers::Count
+                )
+                .unwrap(),
+                6
+            );
+        }
+
+        #[test]
+        fn test_duplicate_numbers() {
+            let input = "Card 1: 1 2 | 1 1 2\nCard 2: 3 | 3\nCard 3: 4 | 5\nCard 4: 6 | 7";
+            assert_eq!(
+                score_cards(input, &PointsRule::Doubling, DuplicateNumbers::Count).unwrap(),
+                5
+            );
+            assert_eq!(
+                score_cards(input, &PointsRule::Doubling, DuplicateNumbers::Ignore).unwrap(),
+                3
+            );
+            assert_eq!(
+                score_cards(input, &CopiesRule::NextCards, DuplicateNumbers::Count).unwrap(),
+                9
+            );
+            assert_eq!(
+                score_cards(input, &CopiesRule::NextCards, DuplicateNumbers::Ignore).unwrap(),
+                8
+            );
+        }
+
+        #[test]
+        fn test_fibonacci_points() {
+            let points = (0..8)
+                .map(|winning_numbers_count| PointsRule::Fibonacci.points(winning_numbers_count))
+                .collect::<Vec<usize>>();
+            assert_eq!(points, vec![0, 1, 1, 2, 3, 5, 8, 13]);
+        }
+
+        #[test]
+        fn test_custom_rule() {
+            struct WinningCards;
+
+            impl ScoringRule for WinningCards {
+                fn score(&self, winning_numbers_counts: &[usize]) -> usize {
+                    winning_numbers_counts
+                        .iter()
+                        .filter(|&&winning_numbers_count| winning_numbers_count > 0)
+                        .count()
+                }
+            }
+
+            assert_eq!(
+                score_cards(
+                    include_str!("../input_example_1.txt"),
+                    &WinningCards,
+                    DuplicateNumbers::Count
+                )
+                .unwrap(),
+                4
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "wins a copy of itself")]
+        fn test_cyclic_copies() {
+            cards_instances(&[1, 1], |index_card, _| {
+                (index_card + 1) % 2..(index_card + 1) % 2 + 1
+            });
+        }
+    }
+
+    mod provenance {
+        use crate::{
+            cards_provenance, part_2, render_provenance_dot, render_provenance_tree,
+            CardProvenance, CopiesRule, DuplicateNumbers,
+        };
+
+        #[test]
+        fn test_provenance_example() {
+            let provenance = cards_provenance(
+                include_str!("../input_example_1.txt"),
+                CopiesRule::NextCards,
+                DuplicateNumbers::Count,
+            )
+            .unwrap();
+            assert_eq!(
+                provenance[4],
+                CardProvenance {
+                    id: 5,
+                    instances: 14,
+                    copies_from: vec![(1, 1), (3, 4), (4, 8)],
+                }
+            );
+            for card_provenance in &provenance {
+                let copies = card_provenance
+                    .copies_from
+                    .iter()
+                    .map(|&(_, copies)| copies)
+                    .sum::<usize>();
+                assert_eq!(copies, card_provenance.copies());
+            }
+        }
+
+        #[test]
+        fn test_provenance_total() {
+            let input = include_str!("../input.txt");
+            let provenance =
+                cards_provenance(input, CopiesRule::NextCards, DuplicateNumbers::Count).unwrap();
+            let total = provenance
+                .iter()
+                .map(|card_provenance| card_provenance.instances)
+                .sum::<usize>();
+            assert_eq!(total, part_2(input));
+            assert!(render_provenance_tree(&provenance).ends_with("Total: 5659035 cards\n"));
+        }
+
+        #[test]
+        fn test_provenance_tree_example() {
+            let provenance = cards_provenance(
+                include_str!("../input_example_1.txt"),
+                CopiesRule::NextCards,
+                DuplicateNumbers::Count,
+            )
+            .unwrap();
+            assert_eq!(
+                render_provenance_tree(&provenance),
+                "\
+Card 1: 1 instance
+Card 2: 2 instances (1 copy)
+└── 1 copy from Card 1
+Card 3: 4 instances (3 copies)
+├── 1 copy from Card 1
+└── 2 copies from Card 2
+Card 4: 8 instances (7 copies)
+├── 1 copy from Card 1
+├── 2 copies from Card 2
+└── 4 copies from Card 3
+Card 5: 14 instances (13 copies)
+├── 1 copy from Card 1
+├── 4 copies from Card 3
+└── 8 copies from Card 4
+Card 6: 1 instance
+Total: 30 cards
+"
+            );
+        }
+
+        #[test]
+        fn test_provenance_dot_edges() {
+            let provenance = cards_provenance(
+                include_str!("../input_example_1.txt"),
+                CopiesRule::NextCards,
+                DuplicateNumbers::Count,
+            )
+            .unwrap();
+            let dot = render_provenance_dot(&provenance);
+            assert!(dot.starts_with("digraph scratchcards {\n"));
+            assert!(dot.contains("    card_5 [label=\"Card 5\\n14 instances\"];\n"));
+            assert!(dot.contains("    card_4 -> card_5 [label=\"8\"];\n"));
+            assert_eq!(dot.matches(" -> ").count(), 9);
+        }
+    }
+
+    mod shuffled_decks {
+        use std::str::FromStr;
+
+        use crate::{
+            cards_provenance, parse_deck, part_1, part_2, score_cards, score_deck, Card,
+            CopiesRule, DeckParseError, DuplicateNumbers,
+        };
+
+        fn renumber(input: &str, id: impl Fn(usize) -> usize) -> String {
+            input
+                .lines()
+                .map(|line| {
+                    let (header, numbers) = line.split_once(':').unwrap();
+                    let old_id = header.trim_start_matches("Card").trim().parse().unwrap();
+                    format!("Card {}:{}\n", id(old_id), numbers)
+                })
+                .collect()
+        }
+
+        fn shuffle(input: &str) -> String {
+            let lines = input.lines().collect::<Vec<&str>>();
+            let mut indices = (0..lines.len()).collect::<Vec<usize>>();
+            indices.sort_by_key(|&index| (index * 37) % 101);
+            indices
+                .iter()
+                .map(|&index| format!("{}\n", lines[index]))
+                .collect()
+        }
+
+        #[test]
+        fn test_reversed_example() {
+            let input = include_str!("../input_example_1.txt");
+            let reversed = input.lines().rev().collect::<Vec<&str>>().join("\n");
+            assert_eq!(part_1(&reversed), 13);
+            assert_eq!(part_2(&reversed), 30);
+            let provenance =
+                cards_provenance(&reversed, CopiesRule::NextCards, DuplicateNumbers::Count)
+                    .unwrap();
+            assert_eq!(
+                provenance,
+                cards_provenance(input, CopiesRule::NextCards, DuplicateNumbers::Count).unwrap()
+            );
+        }
+
+        #[test]
+        fn test_shuffled_input() {
+            let input = include_str!("../input.txt");
+            let shuffled = shuffle(input);
+            assert_ne!(shuffled, input);
+            assert_eq!(part_1(&shuffled), 24160);
+            assert_eq!(part_2(&shuffled), 5659035);
+        }
+
+        #[test]
+        fn test_non_contiguous_ids() {
+            let input = include_str!("../input_example_1.txt");
+            let with_gaps = shuffle(&renumber(input, |id| id * 10));
+            assert_eq!(part_2(&with_gaps), 30);
+            let from_zero = shuffle(&renumber(input, |id| id - 1));
+            assert_eq!(part_2(&from_zero), 30);
+            let deck = parse_deck(&from_zero).unwrap();
+            assert_eq!(
+                deck.iter().map(|card| card.id).collect::<Vec<usize>>(),
+                vec![0, 1, 2, 3, 4, 5]
+            );
+            assert_eq!(
+                score_deck(&deck, &CopiesRule::NextCards, DuplicateNumbers::Count),
+                30
+            );
+        }
+
+        #[test]
+        fn test_duplicate_ids() {
+            let input = renumber(include_str!("../input_example_1.txt"), |id| id.min(4));
+            let error = parse_deck(&input).unwrap_err();
+            assert_eq!(
+                error,
+                DeckParseError::DuplicateId {
+                    id: 4,
+                    first_line_number: 4,
+                    line_number: 5,
+                }
+            );
+            assert_eq!(error.to_string(), "Line 5: card 4 is already on line 4.");
+        }
+
+        #[test]
+        fn test_invalid_headers() {
+            for line in ["Card x: 2 | 2", "Cart 2: 2 | 2", "2 | 2", " "] {
+                let input = format!("Card 1: 1 | 1\n{line}");
+                let error = parse_deck(&input).unwrap_err();
+                assert_eq!(
+                    error,
+                    DeckParseError::InvalidHeader {
+                        line_number: 2,
+                        line: String::from(line),
+                    }
+                );
+                assert_eq!(
+                    error.to_string(),
+                    format!("Line 2: card should start with `Card <id>: ` (`{line}`).")
+                );
+                assert!(Card::from_str(line).is_err());
+                assert_eq!(
+                    cards_provenance(&input, CopiesRule::NextCards, DuplicateNumbers::Count),
+                    Err(error)
+                );
+                assert_eq!(part_2(&input), 0);
+            }
+        }
+
+        #[test]
+        fn test_corrupt_decks_are_not_scored() {
+            let input = renumber(include_str!("../input_example_1.txt"), |id| id.min(4));
+            assert!(score_cards(&input, &CopiesRule::NextCards, DuplicateNumbers::Count).is_err());
+            assert_eq!(part_1(&input), 0);
+            assert_eq!(part_2(&input), 0);
+        }
+    }
 }
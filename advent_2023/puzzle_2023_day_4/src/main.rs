@@ -1,7 +1,27 @@
-use puzzle_2023_day_4::{part_1, part_2};
+use puzzle_2023_day_4::{
+    cards_provenance, part_1, part_2, render_provenance_dot, render_provenance_tree, CopiesRule,
+    DuplicateNumbers,
+};
 
 fn main() {
     let input = include_str!("../input.txt");
+    let arguments = std::env::args().skip(1).collect::<Vec<String>>();
+    if arguments.iter().any(|argument| argument == "--provenance") {
+        let provenance =
+            match cards_provenance(input, CopiesRule::NextCards, DuplicateNumbers::default()) {
+                Ok(provenance) => provenance,
+                Err(error) => {
+                    eprintln!("{}", error);
+                    return;
+                }
+            };
+        if arguments.iter().any(|argument| argument == "--dot") {
+            print!("{}", render_provenance_dot(&provenance));
+        } else {
+            print!("{}", render_provenance_tree(&provenance));
+        }
+        return;
+    }
     println!("- Day 4 of 2023: Scratchcards -");
     println!("Answer Part 1: {}", part_1(input));
     println!("Answer Part 2: {}", part_2(input));
@@ -1,8 +1,13 @@
 use indicatif::ParallelProgressIterator;
 use rayon::prelude::*;
+use std::fmt;
 use std::ops::Range;
 use std::str::FromStr;
 
+pub const SEED_CATEGORY: &str = "seed";
+
+pub const LOCATION_CATEGORY: &str = "location";
+
 #[derive(Debug, Default, PartialEq, Clone)]
 pub struct RangeConverter {
     pub source_range: Range<usize>,
@@ -45,9 +50,15 @@ impl FromStr for RangeConverter {
         let destination_range_start = *numbers.first().unwrap_or(&0);
         let source_range_start = *numbers.get(1).unwrap_or(&0);
         let range_length = *numbers.get(2).unwrap_or(&0);
+        let source_range_end = source_range_start
+            .checked_add(range_length)
+            .ok_or("Source range end should not overflow `usize`.")?;
+        let destination_range_end = destination_range_start
+            .checked_add(range_length)
+            .ok_or("Destination range end should not overflow `usize`.")?;
         let result = RangeConverter {
-            source_range: source_range_start..(source_range_start + range_length),
-            destination_range: destination_range_start..(destination_range_start + range_length),
+            source_range: source_range_start..source_range_end,
+            destination_range: destination_range_start..destination_range_end,
         };
         Ok(result)
     }
@@ -55,7 +66,8 @@ impl FromStr for RangeConverter {
 
 #[derive(Debug, Default, PartialEq, Clone)]
 pub struct CategoryConverter {
-    pub name: String,
+    pub source_category: String,
+    pub destination_category: String,
     pub ranges_converters: Vec<RangeConverter>,
 }
 
@@ -80,7 +92,8 @@ impl FromStr for CategoryConverter {
     /// 52 50 48
     /// ";
     /// let expected_result = CategoryConverter {
-    ///     name: String::from("seed-to-soil map:"),
+    ///     source_category: String::from("seed"),
+    ///     destination_category: String::from("soil"),
     ///     ranges_converters: vec![
     ///         RangeConverter {
     ///             source_range: 98..100,
@@ -98,19 +111,34 @@ impl FromStr for CategoryConverter {
     /// ```
     fn from_str(string: &str) -> Result<Self, Self::Err> {
         let mut lines = string.trim().lines();
-        let name = lines.next().unwrap_or_default();
+        let (source_category, destination_category) =
+            parse_category_header(lines.next().unwrap_or_default())?;
         let mut ranges_converters = vec![];
         for line in lines {
-            ranges_converters.push(RangeConverter::from_str(line).unwrap_or_default());
+            ranges_converters.push(RangeConverter::from_str(line)?);
         }
         let result = CategoryConverter {
-            name: String::from(name),
+            source_category: String::from(source_category),
+            destination_category: String::from(destination_category),
             ranges_converters,
         };
         Ok(result)
     }
 }
 
+/// Parses a header such as `"seed-to-soil map:"` into its source and destination categories.
+fn parse_category_header(line: &str) -> Result<(&str, &str), &'static str> {
+    let (source_category, destination_category) = line
+        .trim()
+        .strip_suffix(" map:")
+        .and_then(|name| name.split_once("-to-"))
+        .ok_or("Category header should be formatted as `source-to-destination map:`.")?;
+    if source_category.is_empty() || destination_category.is_empty() {
+        return Err("Category header should name a source and a destination category.");
+    }
+    Ok((source_category, destination_category))
+}
+
 impl CategoryConverter {
     pub fn convert(&self, number: usize) -> usize {
         self.ranges_converters
@@ -127,6 +155,153 @@ impl CategoryConverter {
             })
             .unwrap_or(number)
     }
+
+    /// Returns every source number that [`CategoryConverter::convert`] maps to `number`.
+    ///
+    /// When source ranges overlap, only the first range converter containing a source number
+    /// applies, like in [`CategoryConverter::convert`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_5::CategoryConverter;
+    ///
+    /// let category_converter = CategoryConverter::from_str("
+    /// seed-to-soil map:
+    /// 50 98 2
+    /// 52 50 48
+    /// ").unwrap();
+    ///
+    /// assert_eq!(category_converter.convert_inverse(51), vec![99]);
+    /// assert_eq!(category_converter.convert_inverse(98), vec![96]);
+    /// assert_eq!(category_converter.convert_inverse(50), vec![98]);
+    /// assert_eq!(category_converter.convert_inverse(10), vec![10]);
+    /// assert_eq!(category_converter.convert_inverse(99), vec![97]);
+    /// ```
+    pub fn convert_inverse(&self, number: usize) -> Vec<usize> {
+        let mut result = self
+            .ranges_converters
+            .iter()
+            .enumerate()
+            .filter(|(_, range_converter)| range_converter.destination_range.contains(&number))
+            .map(|(index_range_converter, range_converter)| {
+                let source = range_converter.source_range.start
+                    + (number - range_converter.destination_range.start);
+                (index_range_converter, source)
+            })
+            .filter(|&(index_range_converter, source)| {
+                !self.ranges_converters[..index_range_converter].iter().any(
+                    |earlier_range_converter| {
+                        earlier_range_converter.source_range.contains(&source)
+                    },
+                )
+            })
+            .map(|(_, source)| source)
+            .collect::<Vec<usize>>();
+        let is_mapped_by_a_range = self
+            .ranges_converters
+            .iter()
+            .any(|range_converter| range_converter.source_range.contains(&number));
+        if !is_mapped_by_a_range {
+            result.push(number);
+        }
+        result.sort_unstable();
+        result.dedup();
+        result
+    }
+
+    /// Returns the source ranges whose numbers are mapped inside the destination `range`.
+    ///
+    /// When source ranges overlap, only the first range converter containing a source number
+    /// applies, like in [`CategoryConverter::convert`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_5::CategoryConverter;
+    ///
+    /// let category_converter = CategoryConverter::from_str("
+    /// seed-to-soil map:
+    /// 50 98 2
+    /// 52 50 48
+    /// ").unwrap();
+    ///
+    /// assert_eq!(
+    ///     category_converter.convert_inverse_range(45..55),
+    ///     vec![45..53, 98..100],
+    /// );
+    /// ```
+    pub fn convert_inverse_range(&self, range: Range<usize>) -> Vec<Range<usize>> {
+        let mut result = self
+            .ranges_converters
+            .iter()
+            .enumerate()
+            .flat_map(|(index_range_converter, range_converter)| {
+                let start = range.start.max(range_converter.destination_range.start);
+                let end = range.end.min(range_converter.destination_range.end);
+                if start >= end {
+                    return vec![];
+                }
+                let source_start = range_converter.source_range.start
+                    + (start - range_converter.destination_range.start);
+                let source_range = source_start..(source_start + (end - start));
+                let mut source_ranges = vec![source_range];
+                for earlier_range_converter in &self.ranges_converters[..index_range_converter] {
+                    source_ranges = source_ranges
+                        .into_iter()
+                        .flat_map(|source_range| {
+                            subtract_range(&source_range, &earlier_range_converter.source_range)
+                        })
+                        .collect();
+                }
+                source_ranges
+            })
+            .collect::<Vec<Range<usize>>>();
+        let mut unmapped_ranges = vec![range];
+        for range_converter in &self.ranges_converters {
+            unmapped_ranges = unmapped_ranges
+                .into_iter()
+                .flat_map(|unmapped_range| {
+                    subtract_range(&unmapped_range, &range_converter.source_range)
+                })
+                .collect();
+        }
+        result.extend(unmapped_ranges);
+        merge_ranges(result)
+    }
+}
+
+/// Returns the parts of `range` that are not covered by `removed`.
+fn subtract_range(range: &Range<usize>, removed: &Range<usize>) -> Vec<Range<usize>> {
+    if removed.start >= range.end || removed.end <= range.start || removed.is_empty() {
+        return vec![range.clone()];
+    }
+    let mut result = vec![];
+    if range.start < removed.start {
+        result.push(range.start..removed.start);
+    }
+    if removed.end < range.end {
+        result.push(removed.end..range.end);
+    }
+    result
+}
+
+/// Sorts `ranges` and merges the ones that overlap or touch, dropping empty ranges.
+fn merge_ranges(mut ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    ranges.retain(|range| !range.is_empty());
+    ranges.sort_unstable_by_key(|range| (range.start, range.end));
+    let mut result: Vec<Range<usize>> = vec![];
+    for range in ranges {
+        match result.last_mut() {
+            Some(last) if range.start <= last.end => {
+                last.end = last.end.max(range.end);
+            }
+            _ => result.push(range),
+        }
+    }
+    result
 }
 
 #[derive(Debug, Default, PartialEq, Clone)]
@@ -160,7 +335,8 @@ impl FromStr for Almanac {
     /// let expected_result = Almanac {
     ///     seeds: vec![79, 14, 55, 13],
     ///     categories_converters: vec![CategoryConverter {
-    ///         name: String::from("seed-to-soil map:"),
+    ///         source_category: String::from("seed"),
+    ///         destination_category: String::from("soil"),
     ///         ranges_converters: vec![
     ///             RangeConverter {
     ///                 source_range: 98..100,
@@ -178,11 +354,17 @@ impl FromStr for Almanac {
     /// assert_eq!(actual_result, expected_result);
     /// ```
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let mut categories = string.trim().split("\n\n");
+        let mut categories = almanac_blocks(string).into_iter().map(|block| {
+            block
+                .into_iter()
+                .map(|(_, line)| line)
+                .collect::<Vec<&str>>()
+                .join("\n")
+        });
         let seeds = categories
             .next()
             .unwrap_or_default()
-            .strip_prefix("seeds: ")
+            .strip_prefix("seeds:")
             .unwrap_or_default()
             .split_ascii_whitespace()
             .map(|string| {
@@ -191,8 +373,8 @@ impl FromStr for Almanac {
             })
             .collect::<Vec<usize>>();
         let categories_converters = categories
-            .map(|category_string| CategoryConverter::from_str(category_string).unwrap_or_default())
-            .collect::<Vec<CategoryConverter>>();
+            .map(|category| CategoryConverter::from_str(&category))
+            .collect::<Result<Vec<CategoryConverter>, Self::Err>>()?;
         let result = Almanac {
             seeds,
             categories_converters,
@@ -201,20 +383,493 @@ impl FromStr for Almanac {
     }
 }
 
+/// Splits the almanac `input` into its blocks of trimmed lines, along with their line
+/// number, blocks being separated by one or more blank lines.
+fn almanac_blocks(input: &str) -> Vec<Vec<(usize, &str)>> {
+    let mut result: Vec<Vec<(usize, &str)>> = vec![];
+    let mut is_block_start = true;
+    for (index_line, line) in input.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            is_block_start = true;
+            continue;
+        }
+        if is_block_start {
+            result.push(vec![]);
+            is_block_start = false;
+        }
+        if let Some(block) = result.last_mut() {
+            block.push((index_line + 1, line));
+        }
+    }
+    result
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AlmanacRouteError {
+    MissingPath {
+        source_category: String,
+        destination_category: String,
+    },
+    AmbiguousPath {
+        source_category: String,
+        destination_category: String,
+    },
+}
+
+impl fmt::Display for AlmanacRouteError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlmanacRouteError::MissingPath {
+                source_category,
+                destination_category,
+            } => write!(
+                formatter,
+                "No maps lead from `{}` to `{}`.",
+                source_category, destination_category
+            ),
+            AlmanacRouteError::AmbiguousPath {
+                source_category,
+                destination_category,
+            } => write!(
+                formatter,
+                "Several maps lead from `{}` to `{}`.",
+                source_category, destination_category
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AlmanacRouteError {}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum AlmanacIssueKind {
+    /// The source range overlaps the one of an earlier line of the same map.
+    OverlappingSourceRange {
+        other_line_number: usize,
+    },
+    ZeroLengthRange,
+    ArithmeticOverflow,
+    InvalidNumbers,
+    InvalidHeader,
+
+    /// The maps do not form a unique route from seeds to locations.
+    UnresolvedRoute {
+        error: AlmanacRouteError,
+    },
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct AlmanacIssue {
+    /// Line number (starting at 1) in the almanac input.
+    pub line_number: usize,
+    pub line: String,
+    pub kind: AlmanacIssueKind,
+}
+
+impl fmt::Display for AlmanacIssue {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let description = match &self.kind {
+            AlmanacIssueKind::OverlappingSourceRange { other_line_number } => format!(
+                "source range overlaps the one of line {}",
+                other_line_number
+            ),
+            AlmanacIssueKind::ZeroLengthRange => String::from("range length is zero"),
+            AlmanacIssueKind::ArithmeticOverflow => String::from("range end overflows `usize`"),
+            AlmanacIssueKind::InvalidNumbers => {
+                String::from("expected non-negative integers, three per range and seeds by pairs")
+            }
+            AlmanacIssueKind::InvalidHeader => {
+                String::from("expected `seeds:` or a `source-to-destination map:` header")
+            }
+            AlmanacIssueKind::UnresolvedRoute { error } => match error {
+                AlmanacRouteError::MissingPath {
+                    source_category,
+                    destination_category,
+                } => format!(
+                    "no maps lead from `{}` to `{}`",
+                    source_category, destination_category
+                ),
+                AlmanacRouteError::AmbiguousPath {
+                    source_category,
+                    destination_category,
+                } => format!(
+                    "several maps lead from `{}` to `{}`",
+                    source_category, destination_category
+                ),
+            },
+        };
+        write!(
+            formatter,
+            "Line {}: {} (`{}`).",
+            self.line_number, description, self.line
+        )
+    }
+}
+
+/// Locates the route `error` of the almanac `input` on its `seeds:` line.
+fn route_issue(input: &str, error: AlmanacRouteError) -> AlmanacIssue {
+    let (index_line, line) = input
+        .lines()
+        .enumerate()
+        .find(|(_, line)| !line.trim().is_empty())
+        .unwrap_or_default();
+    AlmanacIssue {
+        line_number: index_line + 1,
+        line: String::from(line.trim()),
+        kind: AlmanacIssueKind::UnresolvedRoute { error },
+    }
+}
+
 impl Almanac {
-    pub fn minimum_location(&self) -> usize {
-        self.seeds
+    /// Checks every range line of the almanac `input`, and returns the issues found,
+    /// located by their line number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_5::{Almanac, AlmanacIssue, AlmanacIssueKind};
+    ///
+    /// let input = "seeds: 79 14
+    ///
+    /// seed-to-soil map:
+    /// 50 98 2
+    /// 52 50 0
+    /// 60 99 5";
+    ///
+    /// assert_eq!(
+    ///     Almanac::validate(input),
+    ///     vec![
+    ///         AlmanacIssue {
+    ///             line_number: 5,
+    ///             line: String::from("52 50 0"),
+    ///             kind: AlmanacIssueKind::ZeroLengthRange,
+    ///         },
+    ///         AlmanacIssue {
+    ///             line_number: 6,
+    ///             line: String::from("60 99 5"),
+    ///             kind: AlmanacIssueKind::OverlappingSourceRange { other_line_number: 4 },
+    ///         },
+    ///     ],
+    /// );
+    /// ```
+    pub fn validate(input: &str) -> Vec<AlmanacIssue> {
+        let mut issues = vec![];
+        for (index_block, block) in almanac_blocks(input).into_iter().enumerate() {
+            let Some((&(header_line_number, header), lines)) = block.split_first() else {
+                continue;
+            };
+            let is_valid_header = if index_block == 0 {
+                header.starts_with("seeds:")
+            } else {
+                parse_category_header(header).is_ok()
+            };
+            if !is_valid_header {
+                issues.push(AlmanacIssue {
+                    line_number: header_line_number,
+                    line: String::from(header),
+                    kind: AlmanacIssueKind::InvalidHeader,
+                });
+            }
+            if is_valid_header && index_block == 0 {
+                Almanac::validate_seeds_lines(&block, &mut issues);
+            }
+            if is_valid_header && index_block > 0 {
+                Almanac::validate_map_lines(lines, &mut issues);
+            }
+        }
+        issues
+    }
+
+    /// Checks the lines of the seeds, along with their line number, the first one starting
+    /// with `seeds:`, and pushes the issues found into `issues`.
+    ///
+    /// The seeds must also read as pairs of a range start and a range length.
+    fn validate_seeds_lines(lines: &[(usize, &str)], issues: &mut Vec<AlmanacIssue>) {
+        let mut seeds: Vec<(usize, &str, usize)> = vec![];
+        let mut are_numbers_valid = true;
+        for &(line_number, line) in lines {
+            let numbers = line
+                .strip_prefix("seeds:")
+                .unwrap_or(line)
+                .split_ascii_whitespace()
+                .map(|string| string.parse::<usize>().ok())
+                .collect::<Option<Vec<usize>>>();
+            let Some(numbers) = numbers else {
+                issues.push(AlmanacIssue {
+                    line_number,
+                    line: String::from(line),
+                    kind: AlmanacIssueKind::InvalidNumbers,
+                });
+                are_numbers_valid = false;
+                continue;
+            };
+            seeds.extend(numbers.into_iter().map(|seed| (line_number, line, seed)));
+        }
+        if !are_numbers_valid {
+            return;
+        }
+        for pair in seeds.chunks(2) {
+            let (kind, (line_number, line)) = match *pair {
+                [(_, _, range_start), (line_number, line, range_length)] => {
+                    if range_start.checked_add(range_length).is_some() {
+                        continue;
+                    }
+                    (AlmanacIssueKind::ArithmeticOverflow, (line_number, line))
+                }
+                [(line_number, line, _)] => (AlmanacIssueKind::InvalidNumbers, (line_number, line)),
+                _ => continue,
+            };
+            issues.push(AlmanacIssue {
+                line_number,
+                line: String::from(line),
+                kind,
+            });
+        }
+    }
+
+    /// Checks the range lines of a map, along with their line number, and pushes the
+    /// issues found into `issues`.
+    fn validate_map_lines(lines: &[(usize, &str)], issues: &mut Vec<AlmanacIssue>) {
+        let mut map_source_ranges: Vec<(usize, Range<usize>)> = vec![];
+        for &(line_number, line) in lines {
+            let mut push_issue = |kind: AlmanacIssueKind| {
+                issues.push(AlmanacIssue {
+                    line_number,
+                    line: String::from(line),
+                    kind,
+                });
+            };
+            let numbers = line
+                .split_ascii_whitespace()
+                .map(|string| string.parse::<usize>().ok())
+                .collect::<Option<Vec<usize>>>();
+            let Some(&[destination_range_start, source_range_start, range_length]) =
+                numbers.as_deref()
+            else {
+                push_issue(AlmanacIssueKind::InvalidNumbers);
+                continue;
+            };
+            if range_length == 0 {
+                push_issue(AlmanacIssueKind::ZeroLengthRange);
+                continue;
+            }
+            let source_range_end = source_range_start.checked_add(range_length);
+            let destination_range_end = destination_range_start.checked_add(range_length);
+            let source_range_end = match (source_range_end, destination_range_end) {
+                (Some(source_range_end), Some(_)) => source_range_end,
+                _ => {
+                    push_issue(AlmanacIssueKind::ArithmeticOverflow);
+                    continue;
+                }
+            };
+            let source_range = source_range_start..source_range_end;
+            if let Some((other_line_number, _)) =
+                map_source_ranges.iter().find(|(_, other_source_range)| {
+                    source_range.start < other_source_range.end
+                        && other_source_range.start < source_range.end
+                })
+            {
+                push_issue(AlmanacIssueKind::OverlappingSourceRange {
+                    other_line_number: *other_line_number,
+                });
+            }
+            map_source_ranges.push((line_number, source_range));
+        }
+    }
+
+    /// Parses the almanac `input` like [`Almanac::from_str`], but refuses it when
+    /// [`Almanac::validate`] finds any issue, or when its maps do not lead from seeds
+    /// to locations.
+    pub fn from_str_strict(input: &str) -> Result<Self, Vec<AlmanacIssue>> {
+        let issues = Almanac::validate(input);
+        if !issues.is_empty() {
+            return Err(issues);
+        }
+        let almanac = Almanac::from_str(input).expect("Validated almanac should parse.");
+        almanac
+            .seed_to_location_route()
+            .map_err(|error| vec![route_issue(input, error)])?;
+        Ok(almanac)
+    }
+
+    /// Returns the chain of [`CategoryConverter`] leading from `source_category` to
+    /// `destination_category`, whatever the order of the maps in the almanac.
+    ///
+    /// Fails when no chain exists, or when more than one does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_5::Almanac;
+    ///
+    /// let almanac = Almanac::from_str("
+    /// seeds: 79
+    ///
+    /// soil-to-fertilizer map:
+    /// 0 15 37
+    ///
+    /// seed-to-soil map:
+    /// 50 98 2
+    /// ").unwrap();
+    /// let route = almanac.route("seed", "fertilizer").unwrap();
+    ///
+    /// assert_eq!(route[0].destination_category, "soil");
+    /// assert_eq!(route[1].destination_category, "fertilizer");
+    /// assert!(almanac.route("fertilizer", "seed").is_err());
+    /// ```
+    pub fn route(
+        &self,
+        source_category: &str,
+        destination_category: &str,
+    ) -> Result<Vec<&CategoryConverter>, AlmanacRouteError> {
+        let mut routes = vec![];
+        self.find_routes(
+            source_category,
+            destination_category,
+            &mut vec![source_category],
+            &mut vec![],
+            &mut routes,
+        );
+        match routes.len() {
+            0 => Err(AlmanacRouteError::MissingPath {
+                source_category: String::from(source_category),
+                destination_category: String::from(destination_category),
+            }),
+            1 => Ok(routes.remove(0)),
+            _ => Err(AlmanacRouteError::AmbiguousPath {
+                source_category: String::from(source_category),
+                destination_category: String::from(destination_category),
+            }),
+        }
+    }
+
+    /// Depth-first search of the routes without cycles, stopping as soon as two are found.
+    fn find_routes<'a: 'b, 'b>(
+        &'a self,
+        current_category: &str,
+        destination_category: &str,
+        visited_categories: &mut Vec<&'b str>,
+        current_route: &mut Vec<&'a CategoryConverter>,
+        routes: &mut Vec<Vec<&'a CategoryConverter>>,
+    ) {
+        if current_category == destination_category {
+            routes.push(current_route.clone());
+            return;
+        }
+        for category_converter in self
+            .categories_converters
+            .iter()
+            .filter(|category_converter| category_converter.source_category == current_category)
+        {
+            if routes.len() > 1 {
+                return;
+            }
+            let next_category = category_converter.destination_category.as_str();
+            if visited_categories.contains(&next_category) {
+                continue;
+            }
+            visited_categories.push(next_category);
+            current_route.push(category_converter);
+            self.find_routes(
+                next_category,
+                destination_category,
+                visited_categories,
+                current_route,
+                routes,
+            );
+            current_route.pop();
+            visited_categories.pop();
+        }
+    }
+
+    /// Converts `number` from `source_category` to `destination_category`.
+    pub fn convert(
+        &self,
+        number: usize,
+        source_category: &str,
+        destination_category: &str,
+    ) -> Result<usize, AlmanacRouteError> {
+        let route = self.route(source_category, destination_category)?;
+        Ok(route
+            .iter()
+            .fold(number, |accumulator, category_converter| {
+                category_converter.convert(accumulator)
+            }))
+    }
+
+    /// Returns the maps leading from seeds to locations.
+    fn seed_to_location_route(&self) -> Result<Vec<&CategoryConverter>, AlmanacRouteError> {
+        self.route(SEED_CATEGORY, LOCATION_CATEGORY)
+    }
+
+    pub fn location(&self, seed: usize) -> Result<usize, AlmanacRouteError> {
+        self.convert(seed, SEED_CATEGORY, LOCATION_CATEGORY)
+    }
+
+    /// Returns every seed whose location is `location`, by chaining
+    /// [`CategoryConverter::convert_inverse`] from the last category to the first.
+    ///
+    /// The seeds returned are not restricted to [`Almanac::seeds`].
+    pub fn seeds_for_location(&self, location: usize) -> Result<Vec<usize>, AlmanacRouteError> {
+        let mut result = self.seed_to_location_route()?.iter().rev().fold(
+            vec![location],
+            |accumulator, category_converter| {
+                accumulator
+                    .iter()
+                    .flat_map(|&number| category_converter.convert_inverse(number))
+                    .collect()
+            },
+        );
+        result.sort_unstable();
+        result.dedup();
+        Ok(result)
+    }
+
+    /// Returns the seed ranges whose locations are inside `locations`, by chaining
+    /// [`CategoryConverter::convert_inverse_range`] from the last category to the first.
+    pub fn seeds_ranges_for_locations(
+        &self,
+        locations: Range<usize>,
+    ) -> Result<Vec<Range<usize>>, AlmanacRouteError> {
+        Ok(self.seed_to_location_route()?.iter().rev().fold(
+            vec![locations],
+            |accumulator, category_converter| {
+                merge_ranges(
+                    accumulator
+                        .into_iter()
+                        .flat_map(|range| category_converter.convert_inverse_range(range))
+                        .collect(),
+                )
+            },
+        ))
+    }
+
+    /// Returns the seeds of [`Almanac::seeds`] that end at the minimum location,
+    /// found by back-propagating from [`Almanac::minimum_location`].
+    pub fn seeds_with_minimum_location(&self) -> Result<Vec<usize>, AlmanacRouteError> {
+        let minimum_location = self.minimum_location()?;
+        Ok(self
+            .seeds_for_location(minimum_location)?
+            .into_iter()
+            .filter(|seed| self.seeds.contains(seed))
+            .collect())
+    }
+
+    pub fn minimum_location(&self) -> Result<usize, AlmanacRouteError> {
+        let route = self.seed_to_location_route()?;
+        Ok(self
+            .seeds
             .par_iter()
             .map(|&seed| {
-                self.categories_converters
-                    .iter()
-                    .fold(seed, |accumulator, category_converter| {
-                        category_converter.convert(accumulator)
-                    })
+                route.iter().fold(seed, |accumulator, category_converter| {
+                    category_converter.convert(accumulator)
+                })
             })
             .progress()
             .min()
-            .unwrap_or_default()
+            .unwrap_or_default())
     }
 
     pub fn set_seeds_as_range_pairs(&mut self) {
@@ -223,10 +878,8 @@ impl Almanac {
             .par_chunks(2)
             .progress()
             .flat_map(|chunk| {
-                if let [range_start, range_length] = chunk {
-                    let start = *range_start;
-                    let length = *range_length;
-                    let range = start..(start + length);
+                if let &[start, length] = chunk {
+                    let range = start..start.checked_add(length).unwrap_or(start);
                     range.into_iter()
                 } else {
                     let empty_range = 0..0;
@@ -239,13 +892,28 @@ impl Almanac {
 
 pub fn part_1(input: &str) -> usize {
     let almanac = Almanac::from_str(input).unwrap_or_default();
-    almanac.minimum_location()
+    almanac.minimum_location().unwrap_or_default()
 }
 
 pub fn part_2(input: &str) -> usize {
     let mut almanac = Almanac::from_str(input).unwrap_or_default();
     almanac.set_seeds_as_range_pairs();
-    almanac.minimum_location()
+    almanac.minimum_location().unwrap_or_default()
+}
+
+pub fn part_1_strict(input: &str) -> Result<usize, Vec<AlmanacIssue>> {
+    let almanac = Almanac::from_str_strict(input)?;
+    almanac
+        .minimum_location()
+        .map_err(|error| vec![route_issue(input, error)])
+}
+
+pub fn part_2_strict(input: &str) -> Result<usize, Vec<AlmanacIssue>> {
+    let mut almanac = Almanac::from_str_strict(input)?;
+    almanac.set_seeds_as_range_pairs();
+    almanac
+        .minimum_location()
+        .map_err(|error| vec![route_issue(input, error)])
 }
 
 #[cfg(test)]
@@ -273,4 +941,303 @@ mod puzzle_2023_day_5_tests {
     fn test_part_2() {
         assert_eq!(part_2(include_str!("../input.txt")), 20283860);
     }
+
+    mod route {
+        use std::str::FromStr;
+
+        use crate::{
+            part_1, part_1_strict, Almanac, AlmanacIssue, AlmanacIssueKind, AlmanacRouteError,
+        };
+
+        fn shuffled_example() -> String {
+            let input = include_str!("../input_example_1.txt");
+            let mut categories = input.trim().split("\n\n").collect::<Vec<&str>>();
+            categories[1..].reverse();
+            categories.join("\n\n")
+        }
+
+        #[test]
+        fn test_part_1_example_shuffled() {
+            assert_eq!(part_1(&shuffled_example()), 35);
+        }
+
+        #[test]
+        fn test_convert_between_any_categories() {
+            let almanac = Almanac::from_str(&shuffled_example()).unwrap();
+            assert_eq!(almanac.convert(81, "soil", "humidity"), Ok(78));
+            assert_eq!(almanac.convert(79, "seed", "seed"), Ok(79));
+        }
+
+        #[test]
+        fn test_missing_path() {
+            let almanac = Almanac::from_str(include_str!("../input_example_1.txt")).unwrap();
+            assert_eq!(
+                almanac.route("location", "seed"),
+                Err(AlmanacRouteError::MissingPath {
+                    source_category: String::from("location"),
+                    destination_category: String::from("seed"),
+                })
+            );
+        }
+
+        #[test]
+        fn test_ambiguous_path() {
+            let input = format!(
+                "{}\n\nsoil-to-water map:\n0 0 10",
+                include_str!("../input_example_1.txt").trim()
+            );
+            let almanac = Almanac::from_str(&input).unwrap();
+            assert_eq!(
+                almanac.route("seed", "location"),
+                Err(AlmanacRouteError::AmbiguousPath {
+                    source_category: String::from("seed"),
+                    destination_category: String::from("location"),
+                })
+            );
+            assert!(almanac.route("water", "location").is_ok());
+        }
+
+        #[test]
+        fn test_invalid_header() {
+            assert!(Almanac::from_str("seeds: 1\n\nseed to soil:\n1 2 3").is_err());
+        }
+
+        #[test]
+        fn test_unresolved_route() {
+            let input = include_str!("../input_example_1.txt")
+                .replace("light-to-temperature", "light-to-temprature");
+            let almanac = Almanac::from_str(&input).unwrap();
+            assert_eq!(
+                almanac.minimum_location(),
+                Err(AlmanacRouteError::MissingPath {
+                    source_category: String::from("seed"),
+                    destination_category: String::from("location"),
+                })
+            );
+            assert!(almanac.location(79).is_err());
+            assert_eq!(
+                part_1_strict(&input),
+                Err(vec![AlmanacIssue {
+                    line_number: 1,
+                    line: String::from("seeds: 79 14 55 13"),
+                    kind: AlmanacIssueKind::UnresolvedRoute {
+                        error: AlmanacRouteError::MissingPath {
+                            source_category: String::from("seed"),
+                            destination_category: String::from("location"),
+                        },
+                    },
+                }])
+            );
+        }
+
+        #[test]
+        fn test_empty_blocks_are_skipped() {
+            let input = include_str!("../input_example_1.txt").replace("\n\n", "\n\n\n\n");
+            assert_eq!(part_1(&input), 35);
+        }
+    }
+
+    mod validation {
+        use std::str::FromStr;
+
+        use crate::{
+            part_1, part_1_strict, part_2, part_2_strict, Almanac, AlmanacIssue, AlmanacIssueKind,
+            RangeConverter,
+        };
+
+        #[test]
+        fn test_valid_inputs() {
+            assert_eq!(
+                Almanac::validate(include_str!("../input_example_1.txt")),
+                vec![]
+            );
+            assert_eq!(Almanac::validate(include_str!("../input.txt")), vec![]);
+            assert_eq!(
+                part_1_strict(include_str!("../input_example_1.txt")),
+                Ok(35)
+            );
+        }
+
+        #[test]
+        fn test_blank_lines_are_split_like_validation() {
+            let input = include_str!("../input_example_1.txt");
+            for input in [
+                input.replace("\n\n", "\n\n\n\n"),
+                input.replace("\n\n", "\n  \n"),
+                input.replace('\n', "\r\n"),
+            ] {
+                assert_eq!(Almanac::validate(&input), vec![]);
+                assert_eq!(part_1_strict(&input), Ok(35));
+                assert_eq!(part_1(&input), 35);
+            }
+        }
+
+        #[test]
+        fn test_range_converter_overflow() {
+            let string = format!("0 {} 2", usize::MAX);
+            assert!(RangeConverter::from_str(&string).is_err());
+            let input = format!("seeds: 1 2\n\nseed-to-soil map:\n{string}");
+            assert!(Almanac::from_str(&input).is_err());
+        }
+
+        #[test]
+        fn test_invalid_seeds() {
+            let seeds_issue = |line: &str, kind: AlmanacIssueKind| {
+                vec![AlmanacIssue {
+                    line_number: 1,
+                    line: String::from(line),
+                    kind,
+                }]
+            };
+            for (line, kind) in [
+                ("seeds: 79 x 55 13", AlmanacIssueKind::InvalidNumbers),
+                ("seeds: 79 14 55", AlmanacIssueKind::InvalidNumbers),
+                (
+                    "seeds: 18446744073709551615 2",
+                    AlmanacIssueKind::ArithmeticOverflow,
+                ),
+            ] {
+                let input =
+                    include_str!("../input_example_1.txt").replace("seeds: 79 14 55 13", line);
+                assert_eq!(Almanac::validate(&input), seeds_issue(line, kind.clone()));
+                assert_eq!(part_2_strict(&input), Err(seeds_issue(line, kind)));
+            }
+            let input = "seeds: 18446744073709551615 2\n\nseed-to-location map:\n0 0 1";
+            assert_eq!(part_2(input), 0);
+        }
+
+        #[test]
+        fn test_issues_are_located() {
+            let input = format!(
+                "seeds: 79 14
+
+seed-to-soil map:
+50 98 2
+52 50 48
+0 {} 2
+
+soil-to-fertilizer map:
+0 15 37
+0 15 0
+10 40 20
+a b c
+
+fertilizer to water:
+1 2 3",
+                usize::MAX
+            );
+            let issues = Almanac::validate(&input);
+            assert_eq!(
+                issues,
+                vec![
+                    AlmanacIssue {
+                        line_number: 6,
+                        line: format!("0 {} 2", usize::MAX),
+                        kind: AlmanacIssueKind::ArithmeticOverflow,
+                    },
+                    AlmanacIssue {
+                        line_number: 10,
+                        line: String::from("0 15 0"),
+                        kind: AlmanacIssueKind::ZeroLengthRange,
+                    },
+                    AlmanacIssue {
+                        line_number: 11,
+                        line: String::from("10 40 20"),
+                        kind: AlmanacIssueKind::OverlappingSourceRange {
+                            other_line_number: 9,
+                        },
+                    },
+                    AlmanacIssue {
+                        line_number: 12,
+                        line: String::from("a b c"),
+                        kind: AlmanacIssueKind::InvalidNumbers,
+                    },
+                    AlmanacIssue {
+                        line_number: 14,
+                        line: String::from("fertilizer to water:"),
+                        kind: AlmanacIssueKind::InvalidHeader,
+                    },
+                ]
+            );
+            assert_eq!(
+                issues[2].to_string(),
+                "Line 11: source range overlaps the one of line 9 (`10 40 20`)."
+            );
+            assert_eq!(part_1_strict(&input), Err(issues));
+        }
+    }
+
+    mod inverse {
+        use std::str::FromStr;
+
+        use crate::{Almanac, CategoryConverter};
+
+        #[test]
+        fn test_convert_inverse_overlapping_source_ranges() {
+            let category_converter = CategoryConverter::from_str(
+                "
+a-to-b map:
+100 0 10
+200 5 10
+",
+            )
+            .unwrap();
+            assert_eq!(category_converter.convert_inverse(202), vec![202]);
+            assert_eq!(category_converter.convert_inverse(205), vec![10, 205]);
+            assert_eq!(
+                category_converter.convert_inverse_range(200..210),
+                vec![10..15, 200..210]
+            );
+            for number in 0..300 {
+                for source in category_converter.convert_inverse(number) {
+                    assert_eq!(category_converter.convert(source), number);
+                }
+                for source_range in category_converter.convert_inverse_range(number..number + 1) {
+                    for source in source_range {
+                        assert_eq!(category_converter.convert(source), number);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn test_seeds_for_location_example() {
+            let almanac = Almanac::from_str(include_str!("../input_example_1.txt")).unwrap();
+            for &seed in &almanac.seeds {
+                let location = almanac.location(seed).unwrap();
+                assert!(almanac
+                    .seeds_for_location(location)
+                    .unwrap()
+                    .contains(&seed));
+            }
+        }
+
+        #[test]
+        fn test_seeds_with_minimum_location_example() {
+            let almanac = Almanac::from_str(include_str!("../input_example_1.txt")).unwrap();
+            assert_eq!(almanac.seeds_with_minimum_location(), Ok(vec![13]));
+        }
+
+        #[test]
+        fn test_seeds_with_minimum_location() {
+            let almanac = Almanac::from_str(include_str!("../input.txt")).unwrap();
+            let seeds = almanac.seeds_with_minimum_location().unwrap();
+            assert!(!seeds.is_empty());
+            for seed in seeds {
+                assert_eq!(almanac.location(seed), Ok(313045984));
+            }
+        }
+
+        #[test]
+        fn test_seeds_ranges_for_locations_example() {
+            let almanac = Almanac::from_str(include_str!("../input_example_1.txt")).unwrap();
+            let seeds_ranges = almanac.seeds_ranges_for_locations(0..47).unwrap();
+            assert!(seeds_ranges.iter().any(|range| range.contains(&82)));
+            for range in seeds_ranges {
+                for seed in range {
+                    assert!(almanac.location(seed).unwrap() < 47);
+                }
+            }
+        }
+    }
 }
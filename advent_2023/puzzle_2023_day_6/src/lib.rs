@@ -1,4 +1,5 @@
 use rayon::prelude::*;
+use std::ops::RangeInclusive;
 
 #[derive(Debug, Default, PartialEq, Clone)]
 pub struct Race {
@@ -21,6 +22,180 @@ impl Race {
             })
             .collect::<Vec<RaceStrategy>>()
     }
+
+    fn distance_in_millimeters_travelled(
+        &self,
+        time_in_milliseconds_holding_button: usize,
+    ) -> usize {
+        RaceStrategy {
+            time_in_milliseconds_holding_button,
+            maximum_time_in_milliseconds: self.maximum_time_in_milliseconds,
+        }
+        .calculate_distance_in_millimeters_travelled()
+    }
+
+    /// Returns the time holding the button that travels the farthest, the shortest one when
+    /// two times travel as far.
+    pub fn best_time_in_milliseconds_holding_button(&self) -> usize {
+        self.maximum_time_in_milliseconds / 2
+    }
+
+    pub fn maximum_distance_in_millimeters(&self) -> usize {
+        self.distance_in_millimeters_travelled(self.best_time_in_milliseconds_holding_button())
+    }
+
+    /// Returns the shortest time holding the button that travels at least
+    /// `distance_in_millimeters`, or [`None`] when no time travels that far.
+    ///
+    /// As the distance only increases until the best time, the time is found by binary search.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_6::Race;
+    ///
+    /// let race = Race {
+    ///     maximum_time_in_milliseconds: 7,
+    ///     best_distance_in_millimeters_recorded: 9,
+    /// };
+    ///
+    /// assert_eq!(race.minimum_time_in_milliseconds_holding_button_reaching(10), Some(2));
+    /// assert_eq!(race.minimum_time_in_milliseconds_holding_button_reaching(13), None);
+    /// ```
+    pub fn minimum_time_in_milliseconds_holding_button_reaching(
+        &self,
+        distance_in_millimeters: usize,
+    ) -> Option<usize> {
+        let mut low = 0;
+        let mut high = self.best_time_in_milliseconds_holding_button();
+        if self.distance_in_millimeters_travelled(high) < distance_in_millimeters {
+            return None;
+        }
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if self.distance_in_millimeters_travelled(middle) >= distance_in_millimeters {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+        Some(low)
+    }
+
+    /// Returns the times holding the button that beat the best distance recorded, or [`None`]
+    /// when the record cannot be beaten.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_6::Race;
+    ///
+    /// let race = Race {
+    ///     maximum_time_in_milliseconds: 7,
+    ///     best_distance_in_millimeters_recorded: 9,
+    /// };
+    ///
+    /// assert_eq!(race.winning_times_in_milliseconds_holding_button(), Some(2..=5));
+    /// assert_eq!(race.margin_of_error(), 4);
+    /// ```
+    pub fn winning_times_in_milliseconds_holding_button(&self) -> Option<RangeInclusive<usize>> {
+        let minimum_time = self.minimum_time_in_milliseconds_holding_button_reaching(
+            self.best_distance_in_millimeters_recorded.checked_add(1)?,
+        )?;
+        // The distance is symmetric: holding `t` or `maximum_time - t` travels as far.
+        Some(minimum_time..=self.maximum_time_in_milliseconds - minimum_time)
+    }
+
+    /// Returns the number of ways to beat the best distance recorded, like
+    /// `get_all_winning_strategies().len()` without listing the strategies, in a time
+    /// logarithmic in the maximum time.
+    pub fn margin_of_error(&self) -> usize {
+        self.winning_times_in_milliseconds_holding_button()
+            .map_or(0, |winning_times| {
+                winning_times.end() - winning_times.start() + 1
+            })
+    }
+
+    /// Returns the shortest time holding the button that beats the best distance recorded by
+    /// at least `margin_in_millimeters`, a margin of 0 or 1 being just a win, or [`None`] when
+    /// no time travels that far.
+    pub fn minimum_time_in_milliseconds_holding_button_to_win_by(
+        &self,
+        margin_in_millimeters: usize,
+    ) -> Option<usize> {
+        self.minimum_time_in_milliseconds_holding_button_reaching(
+            self.best_distance_in_millimeters_recorded
+                .checked_add(margin_in_millimeters.max(1))?,
+        )
+    }
+
+    /// Returns how much longer than the shortest winning time the button must be held to beat
+    /// the best distance recorded by at least `margin_in_millimeters`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_6::Race;
+    ///
+    /// let race = Race {
+    ///     maximum_time_in_milliseconds: 30,
+    ///     best_distance_in_millimeters_recorded: 200,
+    /// };
+    ///
+    /// assert_eq!(race.minimum_time_in_milliseconds_holding_button_to_win_by(1), Some(11));
+    /// assert_eq!(race.additional_time_in_milliseconds_holding_button_to_win_by(10), Some(1));
+    /// assert_eq!(race.additional_time_in_milliseconds_holding_button_to_win_by(26), None);
+    /// ```
+    pub fn additional_time_in_milliseconds_holding_button_to_win_by(
+        &self,
+        margin_in_millimeters: usize,
+    ) -> Option<usize> {
+        let minimum_winning_time = self.minimum_time_in_milliseconds_holding_button_to_win_by(1)?;
+        let minimum_time =
+            self.minimum_time_in_milliseconds_holding_button_to_win_by(margin_in_millimeters)?;
+        Some(minimum_time - minimum_winning_time)
+    }
+
+    /// Returns the distance travelled for every time holding the button, from 0 to the
+    /// maximum time, as CSV with a header.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_6::Race;
+    ///
+    /// let race = Race {
+    ///     maximum_time_in_milliseconds: 3,
+    ///     best_distance_in_millimeters_recorded: 1,
+    /// };
+    ///
+    /// assert_eq!(
+    ///     race.distance_curve_csv(),
+    ///     "\
+    /// time_in_milliseconds_holding_button,distance_in_millimeters_travelled,is_winning
+    /// 0,0,false
+    /// 1,2,true
+    /// 2,2,true
+    /// 3,0,false
+    /// ",
+    /// );
+    /// ```
+    pub fn distance_curve_csv(&self) -> String {
+        let mut result = String::from(
+            "time_in_milliseconds_holding_button,distance_in_millimeters_travelled,is_winning\n",
+        );
+        for time_in_milliseconds_holding_button in 0..=self.maximum_time_in_milliseconds {
+            let distance_in_millimeters_travelled =
+                self.distance_in_millimeters_travelled(time_in_milliseconds_holding_button);
+            result += &format!(
+                "{},{},{}\n",
+                time_in_milliseconds_holding_button,
+                distance_in_millimeters_travelled,
+                distance_in_millimeters_travelled > self.best_distance_in_millimeters_recorded
+            );
+        }
+        result
+    }
 }
 
 #[derive(Debug, Default, PartialEq, Clone)]
@@ -30,16 +205,18 @@ pub struct RaceStrategy {
 }
 
 impl RaceStrategy {
+    /// Returns the distance travelled, saturating at [`usize::MAX`] for races too long
+    /// for the distance to fit.
     pub fn calculate_distance_in_millimeters_travelled(&self) -> usize {
         (self.maximum_time_in_milliseconds - self.time_in_milliseconds_holding_button)
-            * self.time_in_milliseconds_holding_button
+            .saturating_mul(self.time_in_milliseconds_holding_button)
     }
 }
 
 pub fn part_1(input: &str) -> usize {
     fn get_numbers_from_line(line: &str) -> Vec<usize> {
         line.split(':')
-            .last()
+            .next_back()
             .unwrap_or_default()
             .split_ascii_whitespace()
             .map(|value| value.trim())
@@ -70,7 +247,7 @@ pub fn part_1(input: &str) -> usize {
 pub fn part_2(input: &str) -> usize {
     fn get_number_from_line(line: &str) -> usize {
         line.split(':')
-            .last()
+            .next_back()
             .unwrap_or_default()
             .replace(' ', "")
             .trim()
@@ -111,4 +288,148 @@ mod puzzle_2023_day_6_tests {
     fn test_part_2() {
         assert_eq!(part_2(include_str!("../input.txt")), 23501589);
     }
+
+    mod race_queries {
+        use crate::Race;
+
+        fn example_races() -> [Race; 3] {
+            [(7, 9), (15, 40), (30, 200)].map(
+                |(maximum_time_in_milliseconds, best_distance_in_millimeters_recorded)| Race {
+                    maximum_time_in_milliseconds,
+                    best_distance_in_millimeters_recorded,
+                },
+            )
+        }
+
+        #[test]
+        fn test_best_time_and_maximum_distance() {
+            let [race_1, race_2, race_3] = example_races();
+            assert_eq!(race_1.best_time_in_milliseconds_holding_button(), 3);
+            assert_eq!(race_1.maximum_distance_in_millimeters(), 12);
+            assert_eq!(race_2.best_time_in_milliseconds_holding_button(), 7);
+            assert_eq!(race_2.maximum_distance_in_millimeters(), 56);
+            assert_eq!(race_3.maximum_distance_in_millimeters(), 225);
+        }
+
+        #[test]
+        fn test_margin_of_error_matches_winning_strategies() {
+            for race in example_races() {
+                assert_eq!(
+                    race.margin_of_error(),
+                    race.get_all_winning_strategies().len()
+                );
+            }
+            for maximum_time_in_milliseconds in 0..40 {
+                for best_distance_in_millimeters_recorded in 0..420 {
+                    let race = Race {
+                        maximum_time_in_milliseconds,
+                        best_distance_in_millimeters_recorded,
+                    };
+                    let winning_times = (0..=maximum_time_in_milliseconds)
+                        .filter(|&time| {
+                            (maximum_time_in_milliseconds - time) * time
+                                > best_distance_in_millimeters_recorded
+                        })
+                        .collect::<Vec<usize>>();
+                    assert_eq!(race.margin_of_error(), winning_times.len());
+                    assert_eq!(
+                        race.winning_times_in_milliseconds_holding_button()
+                            .map(|winning_times| winning_times.collect::<Vec<usize>>()),
+                        Some(winning_times).filter(|winning_times| !winning_times.is_empty())
+                    );
+                }
+            }
+        }
+
+        #[test]
+        fn test_margin_of_error_part_2() {
+            let race = Race {
+                maximum_time_in_milliseconds: 71530,
+                best_distance_in_millimeters_recorded: 940200,
+            };
+            assert_eq!(race.margin_of_error(), 71503);
+        }
+
+        #[test]
+        fn test_time_to_win_by() {
+            let [_, _, race_3] = example_races();
+            assert_eq!(
+                race_3.minimum_time_in_milliseconds_holding_button_to_win_by(0),
+                Some(11)
+            );
+            assert_eq!(
+                race_3.minimum_time_in_milliseconds_holding_button_to_win_by(10),
+                Some(12)
+            );
+            assert_eq!(
+                race_3.minimum_time_in_milliseconds_holding_button_to_win_by(25),
+                Some(15)
+            );
+            assert_eq!(
+                race_3.minimum_time_in_milliseconds_holding_button_to_win_by(26),
+                None
+            );
+            assert_eq!(
+                race_3.additional_time_in_milliseconds_holding_button_to_win_by(25),
+                Some(4)
+            );
+        }
+
+        #[test]
+        fn test_unbeatable_records_do_not_overflow() {
+            let race = Race {
+                maximum_time_in_milliseconds: 30,
+                best_distance_in_millimeters_recorded: usize::MAX,
+            };
+            assert_eq!(race.winning_times_in_milliseconds_holding_button(), None);
+            assert_eq!(race.margin_of_error(), 0);
+            assert_eq!(
+                race.minimum_time_in_milliseconds_holding_button_to_win_by(0),
+                None
+            );
+            let race = Race {
+                maximum_time_in_milliseconds: 30,
+                best_distance_in_millimeters_recorded: usize::MAX - 1,
+            };
+            assert_eq!(
+                race.minimum_time_in_milliseconds_holding_button_to_win_by(2),
+                None
+            );
+            assert_eq!(
+                race.additional_time_in_milliseconds_holding_button_to_win_by(usize::MAX),
+                None
+            );
+        }
+
+        #[test]
+        fn test_long_races_do_not_overflow() {
+            let race = Race {
+                maximum_time_in_milliseconds: 1 << 33,
+                best_distance_in_millimeters_recorded: 1 << 40,
+            };
+            assert_eq!(race.best_time_in_milliseconds_holding_button(), 1 << 32);
+            assert_eq!(race.maximum_distance_in_millimeters(), usize::MAX);
+            assert_eq!(
+                race.minimum_time_in_milliseconds_holding_button_reaching(1 << 40),
+                Some(129)
+            );
+            assert_eq!(
+                race.minimum_time_in_milliseconds_holding_button_reaching(usize::MAX),
+                Some((1 << 32) - 1)
+            );
+            assert_eq!(race.margin_of_error(), (1 << 33) - 2 * 129 + 1);
+        }
+
+        #[test]
+        fn test_distance_curve_csv() {
+            let [race_1, _, _] = example_races();
+            let csv = race_1.distance_curve_csv();
+            let lines = csv.lines().collect::<Vec<&str>>();
+            assert_eq!(lines.len(), 9);
+            assert_eq!(lines[3], "2,10,true");
+            assert_eq!(lines[8], "7,0,false");
+            let winning_rows = lines.iter().filter(|line| line.ends_with(",true")).count();
+            assert_eq!(winning_rows, race_1.margin_of_error());
+        }
+    }
 }
@@ -0,0 +1,140 @@
+//! Compares the ranking of Part 1 with the packed sort key computed once per hand, and
+//! the original comparison that recomputes the hand types with a `HashMap` each time.
+//!
+//! Run with `cargo bench --package puzzle_2023_day_7`.
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use puzzle_2023_day_7::part_1;
+
+const HANDS_COUNT: usize = 200_000;
+
+const LABELS: [char; 13] = [
+    'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
+];
+
+/// Generates `hands_count` hands with bids, from a linear congruential generator so
+/// that every run ranks the same hands.
+fn generate_hands(hands_count: usize) -> String {
+    let mut state: u64 = 0x2023_0007;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 33) as usize
+    };
+    let mut result = String::new();
+    for _ in 0..hands_count {
+        for _ in 0..5 {
+            result.push(LABELS[next() % LABELS.len()]);
+        }
+        result += &format!(" {}\n", next() % 1000 + 1);
+    }
+    result
+}
+
+fn strength_original(label: char) -> usize {
+    LABELS.len()
+        - LABELS
+            .iter()
+            .position(|&current| current == label)
+            .unwrap_or_default()
+}
+
+fn hand_type_original(cards: &[char]) -> usize {
+    let mut label_counts: HashMap<char, usize> = HashMap::new();
+    for card in cards {
+        if let Some(label_count) = label_counts.get_mut(card) {
+            *label_count += 1;
+        } else {
+            label_counts.insert(*card, 1);
+        }
+    }
+    let keys = label_counts.keys();
+    if keys.len() == 1 {
+        return 7;
+    }
+    if keys.len() == 2 && label_counts.iter().any(|(_, &value)| value == 4) {
+        return 6;
+    }
+    if keys.len() == 2 && label_counts.iter().any(|(_, &value)| value == 3) {
+        return 5;
+    }
+    if keys.len() == 3 && label_counts.iter().any(|(_, &value)| value == 3) {
+        return 4;
+    }
+    if keys.len() == 3 && label_counts.iter().any(|(_, &value)| value == 2) {
+        return 3;
+    }
+    if keys.len() == 5 {
+        return 1;
+    }
+    2
+}
+
+fn part_1_original(input: &str) -> usize {
+    let mut cards_hands = input
+        .lines()
+        .map(|line| {
+            let mut parts = line.split_ascii_whitespace();
+            let cards = parts
+                .next()
+                .unwrap_or_default()
+                .chars()
+                .collect::<Vec<char>>();
+            let bid = parts.next().unwrap_or_default().parse().unwrap_or_default();
+            (cards, bid)
+        })
+        .collect::<Vec<(Vec<char>, usize)>>();
+    cards_hands.sort_by(|(self_cards, _), (other_cards, _)| {
+        if hand_type_original(self_cards) != hand_type_original(other_cards) {
+            return hand_type_original(self_cards).cmp(&hand_type_original(other_cards));
+        }
+        for (&self_card, &other_card) in self_cards.iter().zip(other_cards.iter()) {
+            if self_card != other_card {
+                return strength_original(self_card).cmp(&strength_original(other_card));
+            }
+        }
+        Ordering::Equal
+    });
+    cards_hands
+        .iter()
+        .enumerate()
+        .map(|(index, (_, bid))| bid * (index + 1))
+        .sum()
+}
+
+fn measure(name: &str, hands_count: usize, function: impl Fn() -> usize) -> usize {
+    let mut best = Duration::MAX;
+    let mut result = 0;
+    for _ in 0..5 {
+        let start = Instant::now();
+        result = black_box(function());
+        best = best.min(start.elapsed());
+    }
+    println!(
+        "{:<10} {:>10.2?} ({:>6.1} ns/hand)",
+        name,
+        best,
+        best.as_nanos() as f64 / hands_count as f64
+    );
+    result
+}
+
+fn main() {
+    let input = generate_hands(HANDS_COUNT);
+    println!(
+        "Part 1 on {} generated hands (best of 5 runs):",
+        HANDS_COUNT
+    );
+
+    let original = measure("original", HANDS_COUNT, || {
+        part_1_original(black_box(&input))
+    });
+    let packed = measure("packed", HANDS_COUNT, || part_1(black_box(&input)));
+
+    assert_eq!(original, packed);
+}
@@ -1,5 +1,9 @@
 use core::str::FromStr;
-use std::{cmp::Ordering, collections::HashMap};
+use std::cmp::Ordering;
+use std::fmt;
+
+pub mod poker;
+pub mod statistics;
 
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
 pub struct Card {
@@ -11,12 +15,17 @@ impl Card {
         'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
     ];
 
+    /// Returns the strength of the card, from 1 for `'2'` to 13 for `'A'`, or 0 when its
+    /// label is not a valid label.
     pub fn strength(&self) -> usize {
-        Card::LABELS.len()
-            - Card::LABELS
-                .iter()
-                .position(|&current| current == self.label)
-                .unwrap_or_default()
+        Card::LABELS
+            .iter()
+            .position(|&current| current == self.label)
+            .map_or(0, |position| Card::LABELS.len() - position)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        Card::LABELS.contains(&self.label)
     }
 }
 
@@ -54,6 +63,42 @@ impl CardsHandType {
     pub const fn strength(&self) -> usize {
         *self as usize
     }
+
+    /// Returns the type of a hand from the numbers of cards of each label, in any order.
+    fn from_label_counts(label_counts: &[u8]) -> CardsHandType {
+        let (mut largest, mut second_largest) = (0, 0);
+        for &label_count in label_counts {
+            if label_count > largest {
+                (largest, second_largest) = (label_count, largest);
+            } else if label_count > second_largest {
+                second_largest = label_count;
+            }
+        }
+        match (largest, second_largest) {
+            (5, _) => CardsHandType::FiveOfAKind,
+            (4, _) => CardsHandType::FourOfAKind,
+            (3, 2) => CardsHandType::FullHouse,
+            (3, _) => CardsHandType::ThreeOfAKind,
+            (2, 2) => CardsHandType::TwoPair,
+            (2, _) => CardsHandType::OnePair,
+            _ => CardsHandType::HighCard,
+        }
+    }
+}
+
+impl fmt::Display for CardsHandType {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            CardsHandType::HighCard => "High card",
+            CardsHandType::OnePair => "One pair",
+            CardsHandType::TwoPair => "Two pair",
+            CardsHandType::ThreeOfAKind => "Three of a kind",
+            CardsHandType::FullHouse => "Full house",
+            CardsHandType::FourOfAKind => "Four of a kind",
+            CardsHandType::FiveOfAKind => "Five of a kind",
+        };
+        formatter.pad(name)
+    }
 }
 
 impl Ord for CardsHandType {
@@ -68,65 +113,154 @@ impl PartialOrd for CardsHandType {
     }
 }
 
-#[derive(Debug, Default, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct CardsHand {
-    pub cards: [Card; 5],
+    cards: [Card; 5],
     pub bid: usize,
+    hand_type: CardsHandType,
+    sort_key: u32,
 }
 
 impl CardsHand {
+    /// Number of bits used by the strength of each card in the sort key.
+    const CARD_STRENGTH_BITS: usize = 4;
+
+    /// Creates a hand of `cards` with a `bid`, computing once its type and sort key.
+    ///
+    /// Fails when a card does not have a valid label.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_7::{Card, CardsHand, CardsHandParseErrorKind, CardsHandType};
+    ///
+    /// let cards_hand = CardsHand::new(['K', 'T', 'J', 'J', 'T'].map(Card::from), 220).unwrap();
+    /// assert_eq!(cards_hand.hand_type(), CardsHandType::TwoPair);
+    ///
+    /// let error = CardsHand::new(['X', 'Y', 'Z', 'W', 'V'].map(Card::from), 1).unwrap_err();
+    /// assert_eq!(
+    ///     error.kind,
+    ///     CardsHandParseErrorKind::InvalidLabel {
+    ///         label: 'X',
+    ///         position: 1,
+    ///     },
+    /// );
+    /// ```
+    pub fn new(cards: [Card; 5], bid: usize) -> Result<Self, CardsHandParseError> {
+        if let Some(kind) = CardsHand::invalid_label(&cards) {
+            return Err(CardsHandParseError {
+                line_number: 1,
+                hand: cards.iter().map(|card| card.label).collect(),
+                kind,
+            });
+        }
+        let mut label_counts = [0_u8; Card::LABELS.len() + 1];
+        let mut sort_key: u32 = 0;
+        for card in &cards {
+            let strength = card.strength();
+            label_counts[strength] += 1;
+            sort_key = (sort_key << CardsHand::CARD_STRENGTH_BITS) | strength as u32;
+        }
+        let hand_type = CardsHandType::from_label_counts(&label_counts);
+        sort_key |= (hand_type.strength() as u32) << (cards.len() * CardsHand::CARD_STRENGTH_BITS);
+        Ok(CardsHand {
+            cards,
+            bid,
+            hand_type,
+            sort_key,
+        })
+    }
+
+    /// Returns the error kind of the first card of `cards` without a valid label, if any.
+    fn invalid_label(cards: &[Card]) -> Option<CardsHandParseErrorKind> {
+        cards
+            .iter()
+            .enumerate()
+            .find(|(_, card)| !card.is_valid())
+            .map(|(index_card, card)| CardsHandParseErrorKind::InvalidLabel {
+                label: card.label,
+                position: index_card + 1,
+            })
+    }
+
+    pub fn cards(&self) -> &[Card; 5] {
+        &self.cards
+    }
+
     pub fn hand_type(&self) -> CardsHandType {
-        let mut label_counts: HashMap<char, usize> = HashMap::new();
+        self.hand_type
+    }
+
+    /// Returns the type of the hand when the `'J'` cards are Jokers, acting like whichever
+    /// card makes the hand the strongest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_7::{CardsHand, CardsHandType};
+    ///
+    /// let cards_hand = CardsHand::from_str("KTJJT 220").unwrap();
+    ///
+    /// assert_eq!(cards_hand.hand_type(), CardsHandType::TwoPair);
+    /// assert_eq!(cards_hand.hand_type_with_jokers(), CardsHandType::FourOfAKind);
+    /// ```
+    pub fn hand_type_with_jokers(&self) -> CardsHandType {
+        let mut label_counts = [0_u8; Card::LABELS.len() + 1];
+        let mut jokers_count = 0;
         for card in &self.cards {
-            if let Some(label_count) = label_counts.get_mut(&card.label) {
-                *label_count += 1;
+            if card.label == 'J' {
+                jokers_count += 1;
             } else {
-                label_counts.insert(card.label, 1);
+                label_counts[card.strength()] += 1;
             }
         }
-        let keys = label_counts.keys();
-        if keys.len() == 1 {
-            return CardsHandType::FiveOfAKind;
-        }
-        if keys.len() == 2 && label_counts.iter().any(|(_, &value)| value == 4) {
-            return CardsHandType::FourOfAKind;
-        }
-        if keys.len() == 2 && label_counts.iter().any(|(_, &value)| value == 3) {
-            return CardsHandType::FullHouse;
-        }
-        if keys.len() == 3 && label_counts.iter().any(|(_, &value)| value == 3) {
-            return CardsHandType::ThreeOfAKind;
-        }
-        if keys.len() == 3 && label_counts.iter().any(|(_, &value)| value == 2) {
-            return CardsHandType::TwoPair;
+        if let Some(largest_count) = label_counts.iter_mut().max() {
+            *largest_count += jokers_count;
         }
-        if keys.len() == 5 {
-            return CardsHandType::HighCard;
-        }
-        CardsHandType::OnePair
+        CardsHandType::from_label_counts(&label_counts)
     }
-}
 
-impl Ord for CardsHand {
-    fn cmp(&self, other: &Self) -> Ordering {
-        if self.hand_type() != other.hand_type() {
-            return self
-                .hand_type()
-                .strength()
-                .cmp(&other.hand_type().strength());
-        }
-        for (self_card, other_card) in self.cards.iter().zip(other.cards.iter()) {
-            if self_card != other_card {
-                return self_card.cmp(other_card);
-            }
-        }
-        Ordering::Equal
+    /// Returns the labels of the cards, in the order of the hand.
+    pub fn labels(&self) -> String {
+        self.cards.iter().map(|card| card.label).collect()
     }
-}
 
-impl PartialOrd for CardsHand {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    /// Returns the key ordering the hands by strength: the hand type in the highest bits,
+    /// followed by the strength of each card, 4 bits each, in the order of the hand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_7::CardsHand;
+    ///
+    /// let cards_hand = CardsHand::from_str("32T3K 765").unwrap();
+    ///
+    /// assert_eq!(cards_hand.sort_key(), 0x2_2_1_9_2_c);
+    /// ```
+    pub fn sort_key(&self) -> u32 {
+        self.sort_key
+    }
+
+    /// Compares the strength of the hand with the one of the `other` hand, the bids being
+    /// ignored, so that different hands may be equally strong.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_7::CardsHand;
+    ///
+    /// let cards_hand = CardsHand::from_str("32T3K 765").unwrap();
+    /// let other_cards_hand = CardsHand::from_str("32T3K 12").unwrap();
+    ///
+    /// assert_eq!(cards_hand.strength_cmp(&other_cards_hand), Ordering::Equal);
+    /// assert_ne!(cards_hand, other_cards_hand);
+    /// ```
+    pub fn strength_cmp(&self, other: &Self) -> Ordering {
+        self.sort_key.cmp(&other.sort_key)
     }
 }
 
@@ -146,31 +280,123 @@ impl FromStr for CardsHand {
     /// use puzzle_2023_day_7::{Card, CardsHand, CardsHandType};
     ///
     /// let string = "32T3K 765";
-    /// let expected_result = CardsHand {
-    ///     cards: [Card { label: '3' }, Card { label: '2' }, Card { label: 'T' }, Card { label: '3' }, Card { label: 'K' } ],
-    ///     bid: 765,
-    /// };
+    /// let expected_result = CardsHand::new(
+    ///     [Card { label: '3' }, Card { label: '2' }, Card { label: 'T' }, Card { label: '3' }, Card { label: 'K' } ],
+    ///     765,
+    /// )
+    /// .unwrap();
     ///
     /// let actual_result = CardsHand::from_str(string).unwrap();
-    /// let actual_hand_type = actual_result.hand_type();
     /// assert_eq!(actual_result, expected_result);
+    /// assert_eq!(actual_result.hand_type(), CardsHandType::OnePair);
     /// ```
     fn from_str(string: &str) -> Result<Self, Self::Err> {
+        CardsHand::parse(string).map_err(|error| error.kind.message())
+    }
+}
+
+impl CardsHand {
+    /// Parses a hand `string` like [`CardsHand::from_str`], returning an error naming the hand
+    /// and the offending character or bid when it is ill-formatted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzle_2023_day_7::{CardsHand, CardsHandParseError, CardsHandParseErrorKind};
+    ///
+    /// assert_eq!(
+    ///     CardsHand::parse("32X3K 765"),
+    ///     Err(CardsHandParseError {
+    ///         line_number: 1,
+    ///         hand: String::from("32X3K"),
+    ///         kind: CardsHandParseErrorKind::InvalidLabel {
+    ///             label: 'X',
+    ///             position: 3,
+    ///         },
+    ///     }),
+    /// );
+    /// ```
+    pub fn parse(string: &str) -> Result<Self, CardsHandParseError> {
         let mut input = string.split_ascii_whitespace();
-        let result = CardsHand {
-            cards: array_init::from_iter(input.next().unwrap_or_default().chars().map(Card::from))
-                .unwrap_or_default(),
-            bid: input
-                .next()
-                .unwrap_or_default()
-                .trim()
-                .parse()
-                .unwrap_or_default(),
+        let hand = input.next().unwrap_or_default();
+        let error = |kind: CardsHandParseErrorKind| CardsHandParseError {
+            line_number: 1,
+            hand: String::from(hand),
+            kind,
         };
-        Ok(result)
+        let cards = hand.chars().map(Card::from).collect::<Vec<Card>>();
+        if let Some(kind) = CardsHand::invalid_label(&cards) {
+            return Err(error(kind));
+        }
+        let length = cards.len();
+        let cards = <[Card; 5]>::try_from(cards)
+            .map_err(|_| error(CardsHandParseErrorKind::InvalidLength { length }))?;
+        let bid = input.collect::<Vec<&str>>().join(" ");
+        let bid = bid
+            .parse()
+            .map_err(|_| error(CardsHandParseErrorKind::InvalidBid { bid }))?;
+        CardsHand::new(cards, bid)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum CardsHandParseErrorKind {
+    /// Label of the card at `position` (starting at 1) in the hand is not a valid label.
+    InvalidLabel {
+        label: char,
+        position: usize,
+    },
+    InvalidLength {
+        length: usize,
+    },
+    InvalidBid {
+        bid: String,
+    },
+}
+
+impl CardsHandParseErrorKind {
+    pub const fn message(&self) -> &'static str {
+        match self {
+            CardsHandParseErrorKind::InvalidLabel { .. } => {
+                "Card label should be one of `AKQJT98765432`."
+            }
+            CardsHandParseErrorKind::InvalidLength { .. } => "Hand should have exactly 5 cards.",
+            CardsHandParseErrorKind::InvalidBid { .. } => "Bid should be a non-negative integer.",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CardsHandParseError {
+    /// Line number (starting at 1) in the input.
+    pub line_number: usize,
+    pub hand: String,
+    pub kind: CardsHandParseErrorKind,
+}
+
+impl fmt::Display for CardsHandParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "Line {}, hand `{}`: {}",
+            self.line_number,
+            self.hand,
+            self.kind.message()
+        )?;
+        match &self.kind {
+            CardsHandParseErrorKind::InvalidLabel { label, position } => {
+                write!(formatter, " (`{}` at position {})", label, position)
+            }
+            CardsHandParseErrorKind::InvalidLength { length } => {
+                write!(formatter, " ({} cards)", length)
+            }
+            CardsHandParseErrorKind::InvalidBid { bid } => write!(formatter, " (`{}`)", bid),
+        }
     }
 }
 
+impl std::error::Error for CardsHandParseError {}
+
 #[derive(Debug, Default, PartialEq, Eq, Clone)]
 pub struct CamelCards {
     cards_hands: Vec<CardsHand>,
@@ -179,28 +405,185 @@ pub struct CamelCards {
 impl FromStr for CamelCards {
     type Err = &'static str;
 
+    /// Parses a string `string` to return a value of [`CamelCards`]
+    ///
+    /// If parsing succeeds, return the value inside [`Ok`], otherwise
+    /// when the string is ill-formatted return an error specific to the
+    /// inside [`Err`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_7::CamelCards;
+    ///
+    /// assert_eq!(CamelCards::from_str("32T3K 765\nT55J5 684").unwrap().cards_hands().len(), 2);
+    /// assert_eq!(
+    ///     CamelCards::from_str("32X3K 765\nT55J5 684"),
+    ///     Err("Card label should be one of `AKQJT98765432`."),
+    /// );
+    /// ```
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        Ok(CamelCards {
-            cards_hands: string
-                .trim()
-                .lines()
-                .map(|line| CardsHand::from_str(line.trim()).unwrap_or_default())
-                .collect::<Vec<CardsHand>>(),
+        CamelCards::parse(string).map_err(|error| error.kind.message())
+    }
+}
+
+/// Place of a hand once all the hands are ranked from the weakest to the strongest.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HandRanking {
+    /// Rank of the hand, starting at 1 for the weakest hand.
+    pub rank: usize,
+    pub cards_hand: CardsHand,
+    pub winnings: usize,
+
+    /// Whether another hand is exactly as strong, so that the order between them is arbitrary.
+    pub is_tied: bool,
+}
+
+impl CamelCards {
+    pub fn cards_hands(&self) -> &[CardsHand] {
+        &self.cards_hands
+    }
+
+    /// Ranks the hands from the weakest to the strongest and returns the place and the
+    /// winnings of each of them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_7::{CamelCards, CardsHandType};
+    ///
+    /// let camel_cards = CamelCards::from_str("32T3K 765\nT55J5 684").unwrap();
+    /// let ranking = camel_cards.ranking();
+    ///
+    /// assert_eq!(ranking[1].rank, 2);
+    /// assert_eq!(ranking[1].cards_hand.labels(), "T55J5");
+    /// assert_eq!(ranking[1].cards_hand.hand_type(), CardsHandType::ThreeOfAKind);
+    /// assert_eq!(ranking[1].winnings, 684 * 2);
+    /// ```
+    pub fn ranking(&self) -> Vec<HandRanking> {
+        let mut cards_hands = self.cards_hands.clone();
+        cards_hands.sort_by(CardsHand::strength_cmp);
+        let sort_keys = cards_hands
+            .iter()
+            .map(|cards_hand| cards_hand.sort_key())
+            .collect::<Vec<u32>>();
+        cards_hands
+            .into_iter()
+            .enumerate()
+            .map(|(index, cards_hand)| {
+                let is_tied = (index > 0 && sort_keys[index - 1] == sort_keys[index])
+                    || sort_keys.get(index + 1) == Some(&sort_keys[index]);
+                HandRanking {
+                    rank: index + 1,
+                    winnings: cards_hand.bid * (index + 1),
+                    cards_hand,
+                    is_tied,
+                }
+            })
+            .collect()
+    }
+
+    /// Parses the hands of the `string`, one per line, failing on the first ill-formatted hand.
+    pub fn parse(string: &str) -> Result<Self, CardsHandParseError> {
+        let mut cards_hands = Vec::new();
+        for (index_line, line) in string.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let cards_hand = CardsHand::parse(line).map_err(|error| CardsHandParseError {
+                line_number: index_line + 1,
+                ..error
+            })?;
+            cards_hands.push(cards_hand);
+        }
+        Ok(CamelCards { cards_hands })
+    }
+}
+
+/// Renders the `ranking` as a table with the rank, hand, type, bid and winnings of each
+/// hand, tied hands being flagged, followed by the total winnings.
+///
+/// # Examples
+///
+/// ```
+/// use std::str::FromStr;
+/// use puzzle_2023_day_7::{render_ranking_table, CamelCards};
+///
+/// let camel_cards = CamelCards::from_str("32T3K 765\nT55J5 684\n32T3K 12").unwrap();
+///
+/// assert_eq!(
+///     render_ranking_table(&camel_cards.ranking()),
+///     "\
+/// Rank  Hand   Type             Bid  Winnings
+///    1  32T3K  One pair         765       765  tied
+///    2  32T3K  One pair          12        24  tied
+///    3  T55J5  Three of a kind  684      2052
+/// Total winnings: 2841
+/// ",
+/// );
+/// ```
+pub fn render_ranking_table(ranking: &[HandRanking]) -> String {
+    let rows = ranking
+        .iter()
+        .map(|hand_ranking| {
+            [
+                hand_ranking.rank.to_string(),
+                hand_ranking.cards_hand.labels(),
+                hand_ranking.cards_hand.hand_type().to_string(),
+                hand_ranking.cards_hand.bid.to_string(),
+                hand_ranking.winnings.to_string(),
+            ]
         })
+        .collect::<Vec<[String; 5]>>();
+    let headers = ["Rank", "Hand", "Type", "Bid", "Winnings"];
+    let widths: [usize; 5] = std::array::from_fn(|index_column| {
+        rows.iter()
+            .map(|row| row[index_column].chars().count())
+            .chain([headers[index_column].len()])
+            .max()
+            .unwrap_or_default()
+    });
+    let format_row = |row: [&str; 5]| {
+        format!(
+            "{:>rank$}  {:<hand$}  {:<hand_type$}  {:>bid$}  {:>winnings$}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            row[4],
+            rank = widths[0],
+            hand = widths[1],
+            hand_type = widths[2],
+            bid = widths[3],
+            winnings = widths[4],
+        )
+    };
+
+    let mut result = format_row(headers);
+    result += "\n";
+    for (row, hand_ranking) in rows.iter().zip(ranking) {
+        result += &format_row(row.each_ref().map(String::as_str));
+        if hand_ranking.is_tied {
+            result += "  tied";
+        }
+        result += "\n";
     }
+    let total_winnings = ranking
+        .iter()
+        .map(|hand_ranking| hand_ranking.winnings)
+        .sum::<usize>();
+    result += &format!("Total winnings: {}\n", total_winnings);
+    result
 }
 
 pub fn part_1(input: &str) -> usize {
-    let mut camel_cards = CamelCards::from_str(input).unwrap_or_default();
-    camel_cards.cards_hands.sort();
+    let camel_cards = CamelCards::from_str(input).unwrap_or_default();
     camel_cards
-        .cards_hands
+        .ranking()
         .iter()
-        .enumerate()
-        .map(|(index, card_hand)| {
-            let rank = index + 1;
-            card_hand.bid * rank
-        })
+        .map(|hand_ranking| hand_ranking.winnings)
         .sum()
 }
 
@@ -225,7 +608,7 @@ mod puzzle_2023_day_7_tests {
 
         #[test]
         fn test_five_of_a_kind() {
-            let cards_hand = CardsHand::from_str("AAAAA").unwrap();
+            let cards_hand = CardsHand::from_str("AAAAA 1").unwrap();
             let expected = CardsHandType::FiveOfAKind;
             let actual = cards_hand.hand_type();
             assert_eq!(actual, expected);
@@ -233,7 +616,7 @@ mod puzzle_2023_day_7_tests {
 
         #[test]
         fn test_four_of_a_kind() {
-            let cards_hand = CardsHand::from_str("AA8AA").unwrap();
+            let cards_hand = CardsHand::from_str("AA8AA 1").unwrap();
             let expected = CardsHandType::FourOfAKind;
             let actual = cards_hand.hand_type();
             assert_eq!(actual, expected);
@@ -241,7 +624,7 @@ mod puzzle_2023_day_7_tests {
 
         #[test]
         fn test_full_house() {
-            let cards_hand = CardsHand::from_str("23332").unwrap();
+            let cards_hand = CardsHand::from_str("23332 1").unwrap();
             let expected = CardsHandType::FullHouse;
             let actual = cards_hand.hand_type();
             assert_eq!(actual, expected);
@@ -249,7 +632,7 @@ mod puzzle_2023_day_7_tests {
 
         #[test]
         fn test_three_of_a_kind() {
-            let cards_hand = CardsHand::from_str("TTT98").unwrap();
+            let cards_hand = CardsHand::from_str("TTT98 1").unwrap();
             let expected = CardsHandType::ThreeOfAKind;
             let actual = cards_hand.hand_type();
             assert_eq!(actual, expected);
@@ -257,7 +640,7 @@ mod puzzle_2023_day_7_tests {
 
         #[test]
         fn test_two_pair() {
-            let cards_hand = CardsHand::from_str("23432").unwrap();
+            let cards_hand = CardsHand::from_str("23432 1").unwrap();
             let expected = CardsHandType::TwoPair;
             let actual = cards_hand.hand_type();
             assert_eq!(actual, expected);
@@ -265,7 +648,7 @@ mod puzzle_2023_day_7_tests {
 
         #[test]
         fn test_one_pair() {
-            let cards_hand = CardsHand::from_str("A23A4").unwrap();
+            let cards_hand = CardsHand::from_str("A23A4 1").unwrap();
             let expected = CardsHandType::OnePair;
             let actual = cards_hand.hand_type();
             assert_eq!(actual, expected);
@@ -273,10 +656,193 @@ mod puzzle_2023_day_7_tests {
 
         #[test]
         fn test_high_card() {
-            let cards_hand = CardsHand::from_str("23456").unwrap();
+            let cards_hand = CardsHand::from_str("23456 1").unwrap();
             let expected = CardsHandType::HighCard;
             let actual = cards_hand.hand_type();
             assert_eq!(actual, expected);
         }
     }
+
+    mod sort_keys {
+        use std::cmp::Ordering;
+        use std::str::FromStr;
+
+        use crate::CardsHand;
+
+        #[test]
+        fn test_sort_key_orders_types_first() {
+            let weakest_full_house = CardsHand::from_str("22233 1").unwrap();
+            let strongest_three_of_a_kind = CardsHand::from_str("AAAKQ 1").unwrap();
+            assert!(weakest_full_house.sort_key() > strongest_three_of_a_kind.sort_key());
+            assert_eq!(
+                weakest_full_house.strength_cmp(&strongest_three_of_a_kind),
+                Ordering::Greater
+            );
+        }
+
+        #[test]
+        fn test_sort_key_orders_cards_in_hand_order() {
+            let hands = ["KK677 1", "KTJJT 1", "T55J5 1", "QQQJA 1", "32T3K 1"]
+                .map(|hand| CardsHand::from_str(hand).unwrap());
+            let mut sorted_hands = hands.to_vec();
+            sorted_hands.sort_by(CardsHand::strength_cmp);
+            let sorted_labels = sorted_hands
+                .iter()
+                .map(|hand| {
+                    hand.cards()
+                        .iter()
+                        .map(|card| card.label)
+                        .collect::<String>()
+                })
+                .collect::<Vec<String>>();
+            assert_eq!(
+                sorted_labels,
+                vec!["32T3K", "KTJJT", "KK677", "T55J5", "QQQJA"]
+            );
+        }
+    }
+
+    mod parsing {
+        use std::str::FromStr;
+
+        use crate::{
+            part_1, CamelCards, Card, CardsHand, CardsHandParseError, CardsHandParseErrorKind,
+        };
+
+        #[test]
+        fn test_invalid_label_is_not_an_ace() {
+            assert_eq!(Card::from('X').strength(), 0);
+            assert_eq!(Card::from('2').strength(), 1);
+            assert_eq!(Card::from('A').strength(), 13);
+            assert!(CardsHand::from_str("AAAAX 1").is_err());
+        }
+
+        #[test]
+        fn test_invalid_lengths() {
+            let error = CardsHand::parse("32T3 765").unwrap_err();
+            assert_eq!(
+                error.kind,
+                CardsHandParseErrorKind::InvalidLength { length: 4 }
+            );
+            assert_eq!(
+                error.to_string(),
+                "Line 1, hand `32T3`: Hand should have exactly 5 cards. (4 cards)"
+            );
+            let error = CardsHand::parse("32T3KK 765").unwrap_err();
+            assert_eq!(
+                error.kind,
+                CardsHandParseErrorKind::InvalidLength { length: 6 }
+            );
+            assert!(CardsHand::parse("").is_err());
+        }
+
+        #[test]
+        fn test_invalid_bid() {
+            let error = CardsHand::parse("32T3K 7x5").unwrap_err();
+            assert_eq!(
+                error.kind,
+                CardsHandParseErrorKind::InvalidBid {
+                    bid: String::from("7x5")
+                }
+            );
+            for (string, bid) in [("32T3K", ""), ("32T3K 765 1", "765 1")] {
+                assert_eq!(
+                    CardsHand::parse(string).unwrap_err().kind,
+                    CardsHandParseErrorKind::InvalidBid {
+                        bid: String::from(bid)
+                    }
+                );
+            }
+            assert!(CamelCards::parse("32T3K 765\nT55J5").is_err());
+        }
+
+        #[test]
+        fn test_invalid_hands_are_not_defaulted() {
+            assert!(CardsHand::new(['X', 'Y', 'Z', 'W', 'V'].map(Card::from), 1).is_err());
+            assert_eq!(
+                CamelCards::from_str("32X3K 765\nT55J5 684"),
+                Err("Card label should be one of `AKQJT98765432`.")
+            );
+            assert_eq!(part_1("32X3K 765\nT55J5 684"), 0);
+        }
+
+        #[test]
+        fn test_camel_cards_errors_name_the_line() {
+            assert!(CamelCards::parse(include_str!("../input.txt")).is_ok());
+            let error = CamelCards::parse("32T3K 765\nT55J5 684\nKK6b7 28\n").unwrap_err();
+            assert_eq!(
+                error,
+                CardsHandParseError {
+                    line_number: 3,
+                    hand: String::from("KK6b7"),
+                    kind: CardsHandParseErrorKind::InvalidLabel {
+                        label: 'b',
+                        position: 4,
+                    },
+                }
+            );
+            assert_eq!(
+                error.to_string(),
+                "Line 3, hand `KK6b7`: Card label should be one of `AKQJT98765432`. (`b` at position 4)"
+            );
+        }
+    }
+
+    mod ranking {
+        use std::str::FromStr;
+
+        use crate::{render_ranking_table, CamelCards};
+
+        #[test]
+        fn test_ranking_example() {
+            let camel_cards = CamelCards::from_str(include_str!("../input_example_1.txt")).unwrap();
+            let ranking = camel_cards
+                .ranking()
+                .iter()
+                .map(|hand_ranking| {
+                    (
+                        hand_ranking.rank,
+                        hand_ranking.cards_hand.labels(),
+                        hand_ranking.winnings,
+                    )
+                })
+                .collect::<Vec<(usize, String, usize)>>();
+            assert_eq!(
+                ranking,
+                vec![
+                    (1, String::from("32T3K"), 765),
+                    (2, String::from("KTJJT"), 440),
+                    (3, String::from("KK677"), 84),
+                    (4, String::from("T55J5"), 2736),
+                    (5, String::from("QQQJA"), 2415),
+                ]
+            );
+            assert_eq!(camel_cards.cards_hands()[0].labels(), "32T3K");
+        }
+
+        #[test]
+        fn test_ranking_table_example() {
+            let camel_cards = CamelCards::from_str(include_str!("../input_example_1.txt")).unwrap();
+            assert_eq!(
+                render_ranking_table(&camel_cards.ranking()),
+                "\
+Rank  Hand   Type             Bid  Winnings
+   1  32T3K  One pair         765       765
+   2  KTJJT  Two pair         220       440
+   3  KK677  Two pair          28        84
+   4  T55J5  Three of a kind  684      2736
+   5  QQQJA  Three of a kind  483      2415
+Total winnings: 6440
+"
+            );
+        }
+
+        #[test]
+        fn test_ranking_input_has_no_ties() {
+            let camel_cards = CamelCards::from_str(include_str!("../input.txt")).unwrap();
+            let ranking = camel_cards.ranking();
+            assert_eq!(ranking.len(), 1000);
+            assert!(ranking.iter().all(|hand_ranking| !hand_ranking.is_tied));
+        }
+    }
 }
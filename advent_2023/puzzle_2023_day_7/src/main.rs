@@ -1,7 +1,17 @@
-use puzzle_2023_day_7::part_1;
+use std::str::FromStr;
+
+use puzzle_2023_day_7::{part_1, render_ranking_table, CamelCards};
 
 fn main() {
     let input = include_str!("../input.txt");
+    if std::env::args()
+        .skip(1)
+        .any(|argument| argument == "--ranking")
+    {
+        let camel_cards = CamelCards::from_str(input).unwrap_or_default();
+        print!("{}", render_ranking_table(&camel_cards.ranking()));
+        return;
+    }
     println!("- Day 7 of 2023: Camel Cards -");
     println!("Answer Part 1: {}", part_1(input));
     // println!("Answer Part 2: {}", part_2(input));
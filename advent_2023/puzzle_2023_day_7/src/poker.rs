@@ -0,0 +1,475 @@
+//! Standard poker hands, with suited cards, straights and flushes, ranked like the
+//! Camel Cards hands from the weakest to the strongest.
+
+use core::str::FromStr;
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::Card;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+impl TryFrom<char> for Suit {
+    type Error = &'static str;
+
+    fn try_from(label: char) -> Result<Self, Self::Error> {
+        match label {
+            'C' => Ok(Suit::Clubs),
+            'D' => Ok(Suit::Diamonds),
+            'H' => Ok(Suit::Hearts),
+            'S' => Ok(Suit::Spades),
+            _ => Err("Suit should be one of `CDHS`."),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PokerCard {
+    pub rank: Card,
+    pub suit: Suit,
+}
+
+impl FromStr for PokerCard {
+    type Err = &'static str;
+
+    /// Parses a string `string` to return a value of [`PokerCard`]
+    ///
+    /// If parsing succeeds, return the value inside [`Ok`], otherwise
+    /// when the string is ill-formatted return an error specific to the
+    /// inside [`Err`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_7::Card;
+    /// use puzzle_2023_day_7::poker::{PokerCard, Suit};
+    ///
+    /// let string = "TH";
+    /// let expected_result = PokerCard {
+    ///     rank: Card { label: 'T' },
+    ///     suit: Suit::Hearts,
+    /// };
+    /// let actual_result = PokerCard::from_str(string).unwrap();
+    ///
+    /// assert_eq!(actual_result, expected_result);
+    /// ```
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut characters = string.chars();
+        match (characters.next(), characters.next(), characters.next()) {
+            (Some(rank), Some(suit), None) => {
+                let rank = Card::from(rank);
+                if !rank.is_valid() {
+                    return Err("Card rank should be one of `AKQJT98765432`.");
+                }
+                Ok(PokerCard {
+                    rank,
+                    suit: Suit::try_from(suit)?,
+                })
+            }
+            _ => Err("Card should be formatted as `<rank><suit>`."),
+        }
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum PokerHandCategory {
+    #[default]
+    HighCard = 1,
+    OnePair = 2,
+    TwoPair = 3,
+    ThreeOfAKind = 4,
+    Straight = 5,
+    Flush = 6,
+    FullHouse = 7,
+    FourOfAKind = 8,
+    StraightFlush = 9,
+}
+
+impl PokerHandCategory {
+    pub const fn strength(&self) -> usize {
+        *self as usize
+    }
+}
+
+impl fmt::Display for PokerHandCategory {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            PokerHandCategory::HighCard => "High card",
+            PokerHandCategory::OnePair => "One pair",
+            PokerHandCategory::TwoPair => "Two pair",
+            PokerHandCategory::ThreeOfAKind => "Three of a kind",
+            PokerHandCategory::Straight => "Straight",
+            PokerHandCategory::Flush => "Flush",
+            PokerHandCategory::FullHouse => "Full house",
+            PokerHandCategory::FourOfAKind => "Four of a kind",
+            PokerHandCategory::StraightFlush => "Straight flush",
+        };
+        formatter.pad(name)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PokerHand {
+    cards: [PokerCard; 5],
+    pub bid: usize,
+    category: PokerHandCategory,
+    sort_key: u32,
+}
+
+impl PokerHand {
+    /// Number of bits used by the strength of each rank in the sort key.
+    const RANK_STRENGTH_BITS: usize = 4;
+
+    /// Creates a hand of `cards` with a `bid`, computing once its category and sort key.
+    pub fn new(cards: [PokerCard; 5], bid: usize) -> Self {
+        let mut rank_counts = [0_u8; 14];
+        for card in &cards {
+            rank_counts[card.rank.strength()] += 1;
+        }
+
+        // Ranks by decreasing count, then by decreasing strength: the pairs before their
+        // kickers, the highest pair first.
+        let mut ranks = (1..rank_counts.len())
+            .rev()
+            .filter(|&strength| rank_counts[strength] > 0)
+            .collect::<Vec<usize>>();
+        ranks.sort_by_key(|&strength| std::cmp::Reverse(rank_counts[strength]));
+
+        let is_flush = cards.iter().all(|card| card.suit == cards[0].suit);
+        let straight_top = match ranks[..] {
+            [highest, .., lowest] if ranks.len() == 5 && highest - lowest == 4 => Some(highest),
+            // The wheel, from the Ace to the five, where the Ace is the lowest card.
+            [13, 4, 3, 2, 1] => Some(4),
+            _ => None,
+        };
+        let largest_count = rank_counts[ranks[0]];
+        let category = match (straight_top, is_flush, largest_count, ranks.len()) {
+            (Some(_), true, _, _) => PokerHandCategory::StraightFlush,
+            (_, _, 4, _) => PokerHandCategory::FourOfAKind,
+            (_, _, 3, 2) => PokerHandCategory::FullHouse,
+            (_, true, _, _) => PokerHandCategory::Flush,
+            (Some(_), _, _, _) => PokerHandCategory::Straight,
+            (_, _, 3, _) => PokerHandCategory::ThreeOfAKind,
+            (_, _, 2, 3) => PokerHandCategory::TwoPair,
+            (_, _, 2, _) => PokerHandCategory::OnePair,
+            _ => PokerHandCategory::HighCard,
+        };
+        if let Some(straight_top) = straight_top {
+            ranks = vec![straight_top];
+        }
+
+        let mut sort_key = category.strength() as u32;
+        for index_rank in 0..cards.len() {
+            let strength = ranks.get(index_rank).copied().unwrap_or_default();
+            sort_key = (sort_key << PokerHand::RANK_STRENGTH_BITS) | strength as u32;
+        }
+        PokerHand {
+            cards,
+            bid,
+            category,
+            sort_key,
+        }
+    }
+
+    pub fn cards(&self) -> &[PokerCard; 5] {
+        &self.cards
+    }
+
+    pub fn category(&self) -> PokerHandCategory {
+        self.category
+    }
+
+    /// Returns the key ordering the hands by strength: the category in the highest bits,
+    /// followed by the strength of the ranks that break ties, 4 bits each, the most
+    /// significant first (the top card for straights, the ranks by decreasing number of
+    /// cards then by decreasing strength otherwise).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_7::poker::PokerHand;
+    ///
+    /// let poker_hand = PokerHand::from_str("AS KD 7H 7C 2S").unwrap();
+    ///
+    /// assert_eq!(poker_hand.sort_key(), 0x2_6_d_c_1_0);
+    /// ```
+    pub fn sort_key(&self) -> u32 {
+        self.sort_key
+    }
+
+    /// Compares the strength of the hand with the one of the `other` hand, the suits and
+    /// the bids being ignored, so that different hands may be equally strong.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cmp::Ordering;
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_7::poker::PokerHand;
+    ///
+    /// let hand = PokerHand::from_str("AS KD 7H 7C 2S").unwrap();
+    /// let other_hand = PokerHand::from_str("AH KC 7S 7D 2C").unwrap();
+    ///
+    /// assert_eq!(hand.strength_cmp(&other_hand), Ordering::Equal);
+    /// assert_ne!(hand, other_hand);
+    /// ```
+    pub fn strength_cmp(&self, other: &Self) -> Ordering {
+        self.sort_key.cmp(&other.sort_key)
+    }
+}
+
+impl FromStr for PokerHand {
+    type Err = &'static str;
+
+    /// Parses a string `string` to return a value of [`PokerHand`]
+    ///
+    /// If parsing succeeds, return the value inside [`Ok`], otherwise
+    /// when the string is ill-formatted return an error specific to the
+    /// inside [`Err`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_7::poker::{PokerHand, PokerHandCategory};
+    ///
+    /// let poker_hand = PokerHand::from_str("AS KD 7H 7C 2S 765").unwrap();
+    ///
+    /// assert_eq!(poker_hand.category(), PokerHandCategory::OnePair);
+    /// assert_eq!(poker_hand.bid, 765);
+    /// ```
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        PokerHand::parse(string).map_err(|error| error.kind.message())
+    }
+}
+
+impl PokerHand {
+    /// Parses a hand `string` like [`PokerHand::from_str`], returning an error naming the hand
+    /// and the offending card or bid when it is ill-formatted.
+    pub fn parse(string: &str) -> Result<Self, PokerHandParseError> {
+        let tokens = string.split_ascii_whitespace().collect::<Vec<&str>>();
+        let (cards, bid) = match tokens.len() {
+            6 => (&tokens[..5], Some(tokens[5])),
+            _ => (tokens.as_slice(), None),
+        };
+        let error = |kind: PokerHandParseErrorKind| PokerHandParseError {
+            line_number: 1,
+            hand: cards.join(" "),
+            kind,
+        };
+
+        let mut poker_cards: Vec<PokerCard> = Vec::new();
+        for card in cards {
+            let poker_card = PokerCard::from_str(card).map_err(|_| {
+                error(PokerHandParseErrorKind::InvalidCard {
+                    card: String::from(*card),
+                })
+            })?;
+            if poker_cards.contains(&poker_card) {
+                return Err(error(PokerHandParseErrorKind::DuplicateCard {
+                    card: String::from(*card),
+                }));
+            }
+            poker_cards.push(poker_card);
+        }
+        let length = poker_cards.len();
+        let poker_cards = <[PokerCard; 5]>::try_from(poker_cards)
+            .map_err(|_| error(PokerHandParseErrorKind::InvalidLength { length }))?;
+        let bid = match bid {
+            Some(bid) => bid.parse().map_err(|_| {
+                error(PokerHandParseErrorKind::InvalidBid {
+                    bid: String::from(bid),
+                })
+            })?,
+            None => 0,
+        };
+        Ok(PokerHand::new(poker_cards, bid))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum PokerHandParseErrorKind {
+    InvalidCard { card: String },
+    DuplicateCard { card: String },
+    InvalidLength { length: usize },
+    InvalidBid { bid: String },
+}
+
+impl PokerHandParseErrorKind {
+    pub const fn message(&self) -> &'static str {
+        match self {
+            PokerHandParseErrorKind::InvalidCard { .. } => {
+                "Card should be a rank of `AKQJT98765432` followed by a suit of `CDHS`."
+            }
+            PokerHandParseErrorKind::DuplicateCard { .. } => {
+                "Card should appear at most once per hand."
+            }
+            PokerHandParseErrorKind::InvalidLength { .. } => "Hand should have exactly 5 cards.",
+            PokerHandParseErrorKind::InvalidBid { .. } => "Bid should be a non-negative integer.",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PokerHandParseError {
+    /// Line number (starting at 1) in the input.
+    pub line_number: usize,
+    pub hand: String,
+    pub kind: PokerHandParseErrorKind,
+}
+
+impl fmt::Display for PokerHandParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            formatter,
+            "Line {}, hand `{}`: {}",
+            self.line_number,
+            self.hand,
+            self.kind.message()
+        )?;
+        match &self.kind {
+            PokerHandParseErrorKind::InvalidCard { card }
+            | PokerHandParseErrorKind::DuplicateCard { card } => {
+                write!(formatter, " (`{}`)", card)
+            }
+            PokerHandParseErrorKind::InvalidLength { length } => {
+                write!(formatter, " ({} cards)", length)
+            }
+            PokerHandParseErrorKind::InvalidBid { bid } => write!(formatter, " (`{}`)", bid),
+        }
+    }
+}
+
+impl std::error::Error for PokerHandParseError {}
+
+/// Parses the poker hands of the `input`, one per line followed by its bid, ranks them
+/// from the weakest to the strongest and returns the total winnings.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_7::poker::total_winnings;
+///
+/// let input = "AS KD 7H 7C 2S 10\n2H 3H 4H 5H 6H 20\nKS KH KD 2C 2D 30";
+///
+/// assert_eq!(total_winnings(input), Ok(10 + 30 * 2 + 20 * 3));
+/// ```
+pub fn total_winnings(input: &str) -> Result<usize, PokerHandParseError> {
+    let mut poker_hands = Vec::new();
+    for (index_line, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let poker_hand = PokerHand::parse(line).map_err(|error| PokerHandParseError {
+            line_number: index_line + 1,
+            ..error
+        })?;
+        poker_hands.push(poker_hand);
+    }
+    poker_hands.sort_by(PokerHand::strength_cmp);
+    Ok(poker_hands
+        .iter()
+        .enumerate()
+        .map(|(index, poker_hand)| poker_hand.bid * (index + 1))
+        .sum())
+}
+
+#[cfg(test)]
+mod poker_tests {
+    use super::*;
+
+    fn category(hand: &str) -> PokerHandCategory {
+        PokerHand::from_str(hand).unwrap().category()
+    }
+
+    fn is_stronger(hand: &str, other_hand: &str) -> bool {
+        PokerHand::from_str(hand)
+            .unwrap()
+            .strength_cmp(&PokerHand::from_str(other_hand).unwrap())
+            == Ordering::Greater
+    }
+
+    #[test]
+    fn test_categories() {
+        assert_eq!(category("AS KD 7H 4C 2S"), PokerHandCategory::HighCard);
+        assert_eq!(category("AS KD 7H 7C 2S"), PokerHandCategory::OnePair);
+        assert_eq!(category("AS KD KH 7C 7S"), PokerHandCategory::TwoPair);
+        assert_eq!(category("7D KD 7H 7C 2S"), PokerHandCategory::ThreeOfAKind);
+        assert_eq!(category("9S TD JH QC KS"), PokerHandCategory::Straight);
+        assert_eq!(category("AS 2D 3H 4C 5S"), PokerHandCategory::Straight);
+        assert_eq!(category("AH KH 7H 4H 2H"), PokerHandCategory::Flush);
+        assert_eq!(category("7D KD 7H 7C KS"), PokerHandCategory::FullHouse);
+        assert_eq!(category("7D 7S 7H 7C 2S"), PokerHandCategory::FourOfAKind);
+        assert_eq!(category("TC JC QC KC AC"), PokerHandCategory::StraightFlush);
+        assert_eq!(category("5D 4D 3D 2D AD"), PokerHandCategory::StraightFlush);
+    }
+
+    #[test]
+    fn test_not_straights() {
+        assert_eq!(category("QS KD AH 2C 3S"), PokerHandCategory::HighCard);
+        assert_eq!(category("9S TD JH QC QS"), PokerHandCategory::OnePair);
+    }
+
+    #[test]
+    fn test_kickers() {
+        assert!(is_stronger("7S 7D AH 5C 2S", "7H 7C KH QC JS"));
+        assert!(is_stronger("7S 7D AH 5C 3S", "7H 7C AD 5D 2D"));
+        assert!(is_stronger("8S 8D 2H 2C 3S", "7H 7C 6D 6S AD"));
+        assert!(is_stronger("8S 8D 3H 3C 2S", "8H 8C 2D 2S AD"));
+        assert!(is_stronger("3S 3D 3H 2C 2S", "2H 2C 2D AS AD"));
+        assert!(is_stronger("AH KH 7H 4H 3H", "AS KS 7S 4S 2S"));
+        assert!(is_stronger("KS QS 2S 3S 4S", "AH 2H 3H 4H 6D"));
+    }
+
+    #[test]
+    fn test_ace_is_low_in_the_wheel_only() {
+        assert!(is_stronger("2S 3D 4H 5C 6S", "AS 2D 3H 4C 5S"));
+        assert!(is_stronger("TS JD QH KC AS", "9S TD JH QC KS"));
+        assert!(is_stronger("AS 2D 3H 4C 5S", "AH AC KD QS JS"));
+    }
+
+    #[test]
+    fn test_suits_do_not_break_ties() {
+        let hand = PokerHand::from_str("AS KD 7H 7C 2S").unwrap();
+        let other_hand = PokerHand::from_str("AH KC 7S 7D 2C").unwrap();
+        assert_eq!(hand.strength_cmp(&other_hand), Ordering::Equal);
+        assert_ne!(hand, other_hand);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = PokerHand::parse("AS KD 7X 7C 2S").unwrap_err();
+        assert_eq!(
+            error.kind,
+            PokerHandParseErrorKind::InvalidCard {
+                card: String::from("7X")
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Line 1, hand `AS KD 7X 7C 2S`: Card should be a rank of `AKQJT98765432` followed by a suit of `CDHS`. (`7X`)"
+        );
+        let error = PokerHand::parse("AS KD 7H AS 2S").unwrap_err();
+        assert_eq!(
+            error.kind,
+            PokerHandParseErrorKind::DuplicateCard {
+                card: String::from("AS")
+            }
+        );
+        let error = PokerHand::parse("AS KD 7H 2S").unwrap_err();
+        assert_eq!(
+            error.kind,
+            PokerHandParseErrorKind::InvalidLength { length: 4 }
+        );
+        let error = total_winnings("AS KD 7H 7C 2S 1\n10S KD 7H 7C 2S 2").unwrap_err();
+        assert_eq!(error.line_number, 2);
+    }
+}
@@ -0,0 +1,291 @@
+//! Distribution of the hand types over every possible Camel Cards hand, and statistics
+//! of the hands of an input.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use crate::{CamelCards, Card, CardsHand, CardsHandType};
+
+/// Number of possible hands: 5 cards, each with one of the 13 labels, in order.
+pub const HANDS_COUNT: usize = Card::LABELS.len().pow(5);
+
+#[derive(Debug, Default, PartialEq, Eq, Copy, Clone)]
+pub enum Jokers {
+    /// The `'J'` cards are Jacks.
+    #[default]
+    Disabled,
+
+    /// The `'J'` cards are Jokers, acting like whichever card makes the hand the strongest.
+    Wild,
+}
+
+impl Jokers {
+    pub fn hand_type(&self, cards_hand: &CardsHand) -> CardsHandType {
+        match self {
+            Jokers::Disabled => cards_hand.hand_type(),
+            Jokers::Wild => cards_hand.hand_type_with_jokers(),
+        }
+    }
+}
+
+/// Returns every possible hand, [`HANDS_COUNT`] in total.
+pub fn all_hands() -> impl Iterator<Item = [Card; 5]> {
+    (0..HANDS_COUNT).map(|mut index_hand| {
+        std::array::from_fn(|_| {
+            let label = Card::LABELS[index_hand % Card::LABELS.len()];
+            index_hand /= Card::LABELS.len();
+            Card::from(label)
+        })
+    })
+}
+
+/// Returns the number of possible hands of each type.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_2023_day_7::CardsHandType;
+/// use puzzle_2023_day_7::statistics::{hand_types_distribution, Jokers, HANDS_COUNT};
+///
+/// let distribution = hand_types_distribution(Jokers::Disabled);
+///
+/// assert_eq!(distribution[&CardsHandType::FiveOfAKind], 13);
+/// assert_eq!(distribution.values().sum::<usize>(), HANDS_COUNT);
+/// ```
+pub fn hand_types_distribution(jokers: Jokers) -> BTreeMap<CardsHandType, usize> {
+    let mut result = BTreeMap::new();
+    for cards in all_hands() {
+        let cards_hand = CardsHand::new(cards, 0).expect("Generated hands should be valid.");
+        let hand_type = jokers.hand_type(&cards_hand);
+        *result.entry(hand_type).or_insert(0) += 1;
+    }
+    result
+}
+
+/// Statistics of the hands of an input.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct InputStatistics {
+    /// Number of hands of each type.
+    pub hand_types: BTreeMap<CardsHandType, usize>,
+
+    /// Bids of the hands, sorted in increasing order.
+    pub bids: Vec<usize>,
+}
+
+impl InputStatistics {
+    /// Computes the statistics of the hands of `camel_cards`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::str::FromStr;
+    /// use puzzle_2023_day_7::{CamelCards, CardsHandType};
+    /// use puzzle_2023_day_7::statistics::{InputStatistics, Jokers};
+    ///
+    /// let camel_cards = CamelCards::from_str("32T3K 765\nT55J5 684\nKK677 28").unwrap();
+    /// let statistics = InputStatistics::new(&camel_cards, Jokers::Disabled);
+    ///
+    /// assert_eq!(statistics.hand_types[&CardsHandType::OnePair], 1);
+    /// assert_eq!(statistics.median_bid(), Some(684));
+    /// ```
+    pub fn new(camel_cards: &CamelCards, jokers: Jokers) -> Self {
+        let mut result = InputStatistics::default();
+        for cards_hand in camel_cards.cards_hands() {
+            *result
+                .hand_types
+                .entry(jokers.hand_type(cards_hand))
+                .or_insert(0) += 1;
+            result.bids.push(cards_hand.bid);
+        }
+        result.bids.sort_unstable();
+        result
+    }
+
+    pub fn minimum_bid(&self) -> Option<usize> {
+        self.bids.first().copied()
+    }
+
+    pub fn maximum_bid(&self) -> Option<usize> {
+        self.bids.last().copied()
+    }
+
+    /// Returns the middle bid, the lower one of the two middle bids for an even number of hands.
+    pub fn median_bid(&self) -> Option<usize> {
+        if self.bids.is_empty() {
+            return None;
+        }
+        self.bids.get((self.bids.len() - 1) / 2).copied()
+    }
+
+    pub fn mean_bid(&self) -> Option<f64> {
+        if self.bids.is_empty() {
+            return None;
+        }
+        Some(self.bids.iter().sum::<usize>() as f64 / self.bids.len() as f64)
+    }
+
+    /// Returns the number of bids in each bucket of `bucket_width`, from the bucket of the
+    /// lowest bid to the bucket of the highest bid, empty buckets included.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bucket_width` is 0.
+    pub fn bid_histogram(&self, bucket_width: usize) -> Vec<(Range<usize>, usize)> {
+        assert!(bucket_width > 0, "Bucket width should be positive.");
+        let (Some(minimum_bid), Some(maximum_bid)) = (self.minimum_bid(), self.maximum_bid())
+        else {
+            return Vec::new();
+        };
+        let first_bucket = minimum_bid / bucket_width;
+        let mut result = (first_bucket..=maximum_bid / bucket_width)
+            .map(|bucket| (bucket * bucket_width..(bucket + 1) * bucket_width, 0))
+            .collect::<Vec<(Range<usize>, usize)>>();
+        for bid in &self.bids {
+            result[bid / bucket_width - first_bucket].1 += 1;
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod statistics_tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    /// Classifies the hand from the number of pairs of cards with the same label among its
+    /// 10 pairs of cards, independently of the label counts used by [`CardsHand`].
+    fn brute_force_hand_type(labels: &[char]) -> CardsHandType {
+        let mut equal_pairs_count = 0;
+        for index_first in 0..labels.len() {
+            for index_second in index_first + 1..labels.len() {
+                if labels[index_first] == labels[index_second] {
+                    equal_pairs_count += 1;
+                }
+            }
+        }
+        match equal_pairs_count {
+            10 => CardsHandType::FiveOfAKind,
+            6 => CardsHandType::FourOfAKind,
+            4 => CardsHandType::FullHouse,
+            3 => CardsHandType::ThreeOfAKind,
+            2 => CardsHandType::TwoPair,
+            1 => CardsHandType::OnePair,
+            _ => CardsHandType::HighCard,
+        }
+    }
+
+    /// Classifies the hand with Jokers by trying every label for every Joker.
+    fn brute_force_hand_type_with_jokers(labels: &mut [char], index_start: usize) -> CardsHandType {
+        let Some(index_joker) = (index_start..labels.len()).find(|&index| labels[index] == 'J')
+        else {
+            return brute_force_hand_type(labels);
+        };
+        let mut result = CardsHandType::HighCard;
+        for label in Card::LABELS {
+            labels[index_joker] = label;
+            result = result.max(brute_force_hand_type_with_jokers(labels, index_joker + 1));
+        }
+        labels[index_joker] = 'J';
+        result
+    }
+
+    fn distribution(counts: [usize; 7]) -> BTreeMap<CardsHandType, usize> {
+        BTreeMap::from([
+            (CardsHandType::HighCard, counts[0]),
+            (CardsHandType::OnePair, counts[1]),
+            (CardsHandType::TwoPair, counts[2]),
+            (CardsHandType::ThreeOfAKind, counts[3]),
+            (CardsHandType::FullHouse, counts[4]),
+            (CardsHandType::FourOfAKind, counts[5]),
+            (CardsHandType::FiveOfAKind, counts[6]),
+        ])
+    }
+
+    #[test]
+    fn test_all_hands_are_distinct() {
+        let mut labels = all_hands()
+            .map(|cards| cards.map(|card| card.label))
+            .collect::<Vec<[char; 5]>>();
+        labels.sort_unstable();
+        labels.dedup();
+        assert_eq!(labels.len(), HANDS_COUNT);
+    }
+
+    #[test]
+    fn test_distribution_without_jokers() {
+        assert_eq!(
+            hand_types_distribution(Jokers::Disabled),
+            distribution([154440, 171600, 25740, 17160, 1560, 780, 13])
+        );
+    }
+
+    #[test]
+    fn test_distribution_with_jokers() {
+        assert_eq!(
+            hand_types_distribution(Jokers::Wild),
+            distribution([95040, 178200, 19800, 66000, 3300, 8580, 373])
+        );
+    }
+
+    #[test]
+    fn test_hand_types_against_brute_force() {
+        for cards in all_hands() {
+            let mut labels = cards.clone().map(|card| card.label);
+            let cards_hand = CardsHand::new(cards, 0).unwrap();
+            assert_eq!(cards_hand.hand_type(), brute_force_hand_type(&labels));
+            assert_eq!(
+                cards_hand.hand_type_with_jokers(),
+                brute_force_hand_type_with_jokers(&mut labels, 0)
+            );
+        }
+    }
+
+    #[test]
+    fn test_input_statistics_example() {
+        let camel_cards = CamelCards::from_str(include_str!("../input_example_1.txt")).unwrap();
+        let statistics = InputStatistics::new(&camel_cards, Jokers::Disabled);
+        assert_eq!(
+            statistics.hand_types,
+            BTreeMap::from([
+                (CardsHandType::OnePair, 1),
+                (CardsHandType::TwoPair, 2),
+                (CardsHandType::ThreeOfAKind, 2),
+            ])
+        );
+        assert_eq!(statistics.bids, vec![28, 220, 483, 684, 765]);
+        assert_eq!(statistics.minimum_bid(), Some(28));
+        assert_eq!(statistics.maximum_bid(), Some(765));
+        assert_eq!(statistics.median_bid(), Some(483));
+        assert_eq!(statistics.mean_bid(), Some(436.0));
+        assert_eq!(
+            statistics.bid_histogram(250),
+            vec![(0..250, 2), (250..500, 1), (500..750, 1), (750..1000, 1)]
+        );
+
+        let statistics = InputStatistics::new(&camel_cards, Jokers::Wild);
+        assert_eq!(
+            statistics.hand_types,
+            BTreeMap::from([
+                (CardsHandType::OnePair, 1),
+                (CardsHandType::TwoPair, 1),
+                (CardsHandType::FourOfAKind, 3),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_input_statistics() {
+        let camel_cards = CamelCards::from_str(include_str!("../input.txt")).unwrap();
+        let statistics = InputStatistics::new(&camel_cards, Jokers::Disabled);
+        assert_eq!(statistics.hand_types.values().sum::<usize>(), 1000);
+        assert_eq!(statistics.bids.len(), 1000);
+        let histogram = statistics.bid_histogram(100);
+        assert_eq!(
+            histogram.iter().map(|(_, count)| count).sum::<usize>(),
+            1000
+        );
+        assert!(InputStatistics::default().bid_histogram(100).is_empty());
+        assert_eq!(InputStatistics::default().median_bid(), None);
+    }
+}